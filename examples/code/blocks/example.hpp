@@ -0,0 +1,27 @@
+/*! Example function with various block level elements in the docs
+ *
+ *  \par A paragraph block with a title
+ *  The text of the paragraph block.
+ *
+ *  \parblock
+ *  First paragraph inside a parblock.
+ *
+ *  Second paragraph inside the same parblock.
+ *  \endparblock
+ *
+ *  > A Markdown style block quote
+ *  > which spans multiple lines.
+ *
+ *  <dl>
+ *    <dt>First term</dt>
+ *    <dd>Definition of the first term.</dd>
+ *    <dt>Second term</dt>
+ *    <dd>Definition of the second term.</dd>
+ *  </dl>
+ *
+ *  <details>
+ *    <summary>Click to show more</summary>
+ *    Content which is hidden until the summary is clicked.
+ *  </details>
+ */
+void example_function_with_blocks(int arg1, bool arg2);
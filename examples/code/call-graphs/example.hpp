@@ -0,0 +1,29 @@
+
+/*! Reads a value from the sensor
+ */
+inline int read_sensor()
+{
+    return 42;
+}
+
+/*! Converts a raw sensor value into degrees
+ */
+inline double to_degrees(int raw)
+{
+    return raw * 0.5;
+}
+
+/*! Reads the current temperature in degrees
+ */
+inline double read_temperature()
+{
+    //! The sensor is read once per call as it has no internal buffer.
+    return to_degrees(read_sensor());
+}
+
+/*! Reads the temperature and reports whether it is too hot
+ */
+inline bool is_too_hot()
+{
+    return read_temperature() > 30.0;
+}
@@ -11,4 +11,32 @@ public:
     */
     void method_1(int arg1, bool arg2);
 
+    /*! Creates an example from a value
+     */
+    explicit Example(int value);
+
+    /*! Returns the value
+     */
+    constexpr int value() const noexcept;
+
+    /*! Takes the contents of a temporary example
+     */
+    int take() &&;
+
+    /*! Describes the example
+     */
+    virtual const char* describe() const;
+
+};
+
+/*! An example which can't be extended further
+*/
+class FinalExample : public Example
+{
+public:
+
+    /*! Describes the final example
+     */
+    const char* describe() const final;
+
 };
@@ -0,0 +1,25 @@
+#include <concepts>
+#include <type_traits>
+
+/*! Types which support the arithmetic operators
+*/
+template<typename T>
+concept Number = std::is_arithmetic_v<T>;
+
+/*! Types which can be compared for equality with another type
+*/
+template<typename T, typename U>
+concept EqualityComparableWith = requires(T a, U b) {
+    { a == b } -> std::convertible_to<bool>;
+};
+
+/*! A pair of numbers constrained with a requires-clause
+*/
+template<typename T>
+    requires Number<T>
+class NumberPair
+{
+public:
+    T first;
+    T second;
+};
@@ -0,0 +1,11 @@
+/*! \file circle.hpp
+
+  Defines a circle.
+*/
+
+/*! A circle with a radius
+*/
+struct Circle
+{
+    double radius;
+};
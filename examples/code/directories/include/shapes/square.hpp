@@ -0,0 +1,11 @@
+/*! \file square.hpp
+
+  Defines a square.
+*/
+
+/*! A square with equal sides
+*/
+struct Square
+{
+    double side;
+};
@@ -0,0 +1,8 @@
+/*! \file util.hpp
+
+  Utilities shared by all the shapes.
+*/
+
+/*! Returns the larger of the two values
+*/
+double max_value(double first, double second);
@@ -0,0 +1,15 @@
+#include "example.hpp"
+
+#include <iostream>
+
+int main()
+{
+    Counter counter;
+
+    counter.increment();
+    counter.increment();
+
+    std::cout << counter.value() << std::endl;
+
+    return 0;
+}
@@ -0,0 +1,22 @@
+/*! A counter which can be incremented and reset
+*/
+class Counter
+{
+public:
+    /*! Increments the counter by one
+    */
+    void increment();
+
+    /*! Returns the current value of the counter
+    */
+    int value() const;
+
+    /*! Resets the counter to zero
+    */
+    void reset();
+};
+
+/*! \example counter_usage.cpp
+
+  This example shows how to increment a Counter and read its value.
+*/
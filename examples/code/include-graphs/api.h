@@ -0,0 +1,11 @@
+/*! \file api.h
+ *  Public interface of the example library
+ */
+
+#include <stddef.h>
+
+#include "storage.h"
+#include "types.h"
+
+/*! Adds an entry and returns its id */
+entry_id api_add(size_t size);
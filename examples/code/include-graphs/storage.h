@@ -0,0 +1,8 @@
+/*! \file storage.h
+ *  Storage for the entries in the example library
+ */
+
+#include "types.h"
+
+/*! Stores the entry with the given id */
+void storage_store(entry_id id);
@@ -0,0 +1,6 @@
+/*! \file types.h
+ *  Shared types for the example library
+ */
+
+/*! Identifier for an entry */
+typedef int entry_id;
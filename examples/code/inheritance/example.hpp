@@ -0,0 +1,57 @@
+
+/*! A shape that can be drawn
+ */
+class Shape
+{
+public:
+    /*! Draws the shape
+     */
+    virtual void draw() const = 0;
+
+    /*! Returns the number of sides of the shape
+     */
+    int sides() const;
+
+protected:
+    /*! Marks the shape as needing to be redrawn
+     */
+    void invalidate();
+};
+
+/*! Something with a name
+ */
+class Named
+{
+public:
+    /*! Returns the name
+     */
+    const char* name() const;
+};
+
+/*! A circle
+ */
+class Circle : public Shape, protected virtual Named
+{
+public:
+    /*! Draws the circle
+     */
+    void draw() const override;
+};
+
+/*! A square
+ */
+class Square : public Shape
+{
+public:
+    /*! Draws the square
+     */
+    void draw() const override;
+};
+
+/*! A triangle
+ */
+class Triangle : public Shape
+{
+public:
+    void draw() const override;
+};
@@ -0,0 +1,36 @@
+
+/*! A configurable widget
+ */
+class Widget
+{
+public:
+    /*! Returns the width of the widget */
+    int get_width() const;
+
+    /*! Returns the height of the widget */
+    int get_height() const;
+
+    /*! Returns the internal state for debugging */
+    int get_internal_state() const;
+
+    /*! Sets the width of the widget */
+    void set_width(int width);
+
+    /*! The name of the widget */
+    const char* name;
+};
+
+/*! A gadget with a mix of functions and attributes
+ */
+class Gadget
+{
+public:
+    /*! Activates the gadget */
+    void activate();
+
+    /*! The serial number of the gadget */
+    int serial_number;
+
+    /*! Whether the gadget is active */
+    bool active;
+};
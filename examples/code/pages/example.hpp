@@ -0,0 +1,30 @@
+/*! \mainpage Pages Example
+
+  This is the main page of the example, which introduces the \ref getting_started page and the
+  \ref configuration "configuration" section.
+
+  \subpage getting_started
+*/
+
+/*! \page getting_started Getting Started
+
+  \tableofcontents
+
+  This page explains how to get started with the example.
+
+  \section installation Installation
+
+  Copy the header into your project and include it.
+
+  \section configuration Configuration
+
+  Call configure_example() before anything else.
+
+  \subsection configuration_options Options
+
+  \anchor default_options The default options are suitable for most projects.
+*/
+
+/*! Configures the example, see the \ref configuration section and the \ref default_options.
+*/
+void configure_example();
@@ -11,3 +11,20 @@
   \param arg2 the second arg
 */
 void program_listing_example_function(int arg1, bool arg2);
+
+/*! A function with program listings in other languages
+
+  \code{.py}
+  import example
+
+  example.program_listing_python_function("name", [1, 2, 3])
+  \endcode
+
+  Code blocks without an extension are highlighted according to the language of the documented entity.
+
+  \code
+  std::vector<int> values = {1, 2, 3};
+  program_listing_languages_function("name", values);
+  \endcode
+*/
+void program_listing_languages_function(const char* name, const std::vector<int>& values);
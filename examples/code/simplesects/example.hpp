@@ -0,0 +1,20 @@
+/*! Example function with a range of simple sections in the docs
+ *
+ *  \author Jane Doe
+ *  \since 1.2
+ *  \version 2.0
+ *  \date 2023-08-01
+ *  \copyright Docleaf Team
+ *
+ *  \pre The system has been initialised.
+ *  \post The value has been stored.
+ *  \invariant The internal buffer is never empty.
+ *  \remark Safe to call from interrupt handlers.
+ *  \attention Interrupts must be disabled by the caller.
+ *
+ *  \par Algorithm
+ *  Uses a simple linear search.
+ *
+ *  \return The number of stored values.
+ */
+int example_function_with_simple_sections(int value);
@@ -20,3 +20,17 @@ struct ExampleCStructWithReference
   //! An integer field
   int anInt;
 };
+
+/*! A hardware control register with bitfield members.
+*/
+struct ExampleCRegister
+{
+  //! Enables the peripheral
+  unsigned int enable : 1;
+
+  //! Selects the operating mode
+  unsigned int mode : 3;
+
+  //! Reserved bits
+  unsigned int reserved : 28;
+};
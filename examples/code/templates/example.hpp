@@ -0,0 +1,16 @@
+
+/*! A fixed size buffer of values
+ */
+template <typename T, int Size = 16>
+class Buffer
+{
+public:
+    /*! Returns the value at the given index
+     */
+    T get(int index) const;
+};
+
+/*! Returns the larger of the two values
+ */
+template <typename T>
+T max_value(T first, T second);
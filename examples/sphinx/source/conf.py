@@ -42,11 +42,20 @@ for entry in os.listdir("../../code"):
         "xml": f"../code/{entry}/xml/",
     }
 
+docleaf_projects["call-graphs"]["references"] = ["references", "referenced-by", "call-graph", "caller-graph"]
+docleaf_projects["call-graphs"]["inbody_descriptions"] = "rubric"
+docleaf_projects["call-graphs"]["implementation_source"] = "listing"
+
 docleaf_default_project = "nutshell"
 docleaf_domain_by_extension = {"hpp": "cpp", "h": "c"}
 
 docleaf_skip_doxygen_xml_nodes = []
 docleaf_doxygen_skip = ["members:all_caps"]
+docleaf_graphs = ["inheritance", "collaboration", "include", "included-by"]
+docleaf_inherit_docs = True
+docleaf_show_derived_classes = True
+docleaf_show_reimplements = True
+docleaf_member_examples = True
 
 linkcode_resolve = docleaf.doxygen.GitHubLinkResolver(
     root="../../../", user="docleaf-labs", repo="docleaf", branch="main"
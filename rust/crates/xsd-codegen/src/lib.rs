@@ -98,6 +98,7 @@ impl Type {
     }
 }
 
+#[derive(Clone, Copy)]
 enum Wrapper {
     Vec,
     Vec1,
@@ -607,6 +608,24 @@ fn create_struct(node: rx::Node, context: &Context) -> anyhow::Result<TokenStrea
     })
 }
 
+/// Returns the elements within a sequence or choice including those within any nested sequences or choices, eg. the
+/// 'para' and 'sect2' elements in the choice following the 'title' in 'docSect1Type'
+fn get_mixed_content_elements<'a, 'input>(
+    node: rx::Node<'a, 'input>,
+) -> Vec<rx::Node<'a, 'input>> {
+    let mut elements = Vec::new();
+
+    for child in node.children() {
+        match child.tag_name().name() {
+            "element" => elements.push(child),
+            "sequence" | "choice" => elements.append(&mut get_mixed_content_elements(child)),
+            _ => {}
+        }
+    }
+
+    elements
+}
+
 fn create_mixed_content(element: rx::Node) -> anyhow::Result<TokenStream> {
     let type_name = Type::from_str(
         element
@@ -654,7 +673,7 @@ fn create_mixed_content(element: rx::Node) -> anyhow::Result<TokenStream> {
                 let mut new_match_entries = Vec::new();
                 let mut new_match_empty_entries = Vec::new();
 
-                for grand_child in child.children() {
+                for grand_child in get_mixed_content_elements(child) {
                     match (grand_child.attribute("name"), grand_child.attribute("type")) {
                         (Some(name), Some(type_)) => {
                             let name_bytes = proc_macro2::Literal::byte_string(name.as_bytes());
@@ -928,6 +947,21 @@ fn get_elements(element: &rx::Node) -> anyhow::Result<Vec<Element>> {
                     })
                 }
             }
+            "group" => {
+                // A sequence group referenced from within a complex type (eg. docVariableListGroup) is flattened into
+                // the fields of the complex type. The occurrence settings on the reference apply to every element
+                // in the group so repeated groups result in parallel Vecs of the group's elements
+                if let Some(ref_) = child.attribute("ref") {
+                    let group = find_group(child.document(), ref_)?;
+                    let wrapper = get_wrapper(&child)?;
+                    for mut element in get_elements(&group)? {
+                        if wrapper.is_some() {
+                            element.wrapper = wrapper;
+                        }
+                        elements.push(element);
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -935,6 +969,18 @@ fn get_elements(element: &rx::Node) -> anyhow::Result<Vec<Element>> {
     Ok(elements)
 }
 
+fn find_group<'a, 'input>(
+    doc: &'a rx::Document<'input>,
+    name: &str,
+) -> anyhow::Result<rx::Node<'a, 'input>> {
+    doc.root()
+        .first_element_child()
+        .context("Failed to get first element")?
+        .children()
+        .find(|child| child.tag_name().name() == "group" && child.attribute("name") == Some(name))
+        .with_context(|| format!("Failed to find group named '{name}'"))
+}
+
 fn create_restriction(
     name: &str,
     node: rx::Node,
@@ -975,7 +1021,7 @@ fn create_restriction(
     }
 
     Ok(quote! {
-        #[derive(Debug, strum::EnumString, Clone, PartialEq)]
+        #[derive(Debug, strum::EnumString, strum::IntoStaticStr, Clone, PartialEq)]
         pub enum #type_name_id {
             #(#entries),*
         }
@@ -1159,6 +1205,43 @@ fn handle_group(element: rx::Node, context: &Context) -> anyhow::Result<TokenStr
         Choice::Element { .. } => None,
     });
 
+    let element_name_matches = choices.iter().map(|choice| match choice {
+        Choice::Group { type_ } => {
+            let type_id = Type::from_str(type_).to_type_id();
+            quote! {
+                #enum_name_id::#type_id(entry) => entry.element_name(),
+            }
+        }
+        Choice::Element { name, type_ } => {
+            let name_id = Type::from_str(name).to_type_id();
+            match type_ {
+                Some(_) => quote! {
+                    #enum_name_id::#name_id(_) => #name,
+                },
+                None => quote! {
+                    #enum_name_id::#name_id => #name,
+                },
+            }
+        }
+    });
+
+    // Groups for sequences have no choices so we have to match on the value rather than the reference to satisfy
+    // the compiler that the match is exhaustive
+    let element_name_body = if choices.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! {
+            match self {
+                #(#element_name_matches)*
+            }
+        }
+    };
+
+    let element_names = choices.iter().flat_map(|choice| match choice {
+        Choice::Group { .. } => None,
+        Choice::Element { name, .. } => Some(name),
+    });
+
     let mut match_unexpected = quote! {
         _ => anyhow::bail!("Unexpected tag")
     };
@@ -1182,6 +1265,16 @@ fn handle_group(element: rx::Node, context: &Context) -> anyhow::Result<TokenStr
         }
 
         impl #enum_name_id {
+            /// The names of the elements that are direct choices in this group, excluding those from nested groups
+            #[allow(dead_code)]
+            pub const ELEMENT_NAMES: &'static [&'static str] = &[#(#element_names),*];
+
+            /// The name of the xml element that this entry was parsed from
+            #[allow(dead_code)]
+            pub fn element_name(&self) -> &'static str {
+                #element_name_body
+            }
+
             #[allow(unused_variables)]
             fn parse(
                 reader: &mut Reader<&[u8]>,
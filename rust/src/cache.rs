@@ -12,6 +12,7 @@ use crate::doxygen::index::generated as index;
 pub trait Cache {
     fn parse_index(&self, path: PathBuf) -> anyhow::Result<Arc<index::DoxygenType>>;
     fn parse_compound(&self, path: PathBuf) -> anyhow::Result<Arc<compound::DoxygenType>>;
+    fn read_source(&self, path: PathBuf) -> anyhow::Result<Arc<String>>;
 }
 
 /// Cache class exposed to python with no function methods beyond the
@@ -27,7 +28,7 @@ pub struct FileCache {
 #[pymethods]
 impl FileCache {
     #[new]
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             inner: Arc::new(Mutex::new(CacheInner::new())),
         }
@@ -37,10 +38,11 @@ impl FileCache {
 /// Inner cache data which is held with an Arc Mutex by the exposed Cache to
 /// make it easier to access and mutate.
 ///
-/// Cache for xml files so that we don't have to keep re-reading them
+/// Cache for xml files, and the source files that we include, so that we don't have to keep re-reading them
 pub struct CacheInner {
     index_cache: HashMap<PathBuf, Arc<index::DoxygenType>>,
     compound_cache: HashMap<PathBuf, Arc<compound::DoxygenType>>,
+    source_cache: HashMap<PathBuf, Arc<String>>,
 }
 
 impl CacheInner {
@@ -48,6 +50,7 @@ impl CacheInner {
         Self {
             index_cache: HashMap::new(),
             compound_cache: HashMap::new(),
+            source_cache: HashMap::new(),
         }
     }
 }
@@ -80,6 +83,18 @@ impl CacheInner {
             }
         }
     }
+
+    fn read_source(&mut self, path: PathBuf) -> anyhow::Result<Arc<String>> {
+        match self.source_cache.entry(path) {
+            std::collections::hash_map::Entry::Vacant(potential_entry) => {
+                let contents = std::fs::read_to_string(potential_entry.key())?;
+                let contents = Arc::new(contents);
+                potential_entry.insert(contents.clone());
+                Ok(contents)
+            }
+            std::collections::hash_map::Entry::Occupied(entry) => Ok(entry.get().clone()),
+        }
+    }
 }
 
 /// Light weight for cloning due to Arcs
@@ -87,23 +102,24 @@ impl CacheInner {
 #[derive(Clone)]
 pub struct TrackedCache {
     inner: Arc<Mutex<CacheInner>>,
-    xml_paths: Arc<Mutex<HashSet<PathBuf>>>,
+    dependency_paths: Arc<Mutex<HashSet<PathBuf>>>,
 }
 
 #[pymethods]
 impl TrackedCache {
     #[new]
-    fn new(cache: &FileCache) -> Self {
+    pub fn new(cache: &FileCache) -> Self {
         Self {
             inner: cache.inner.clone(),
-            xml_paths: Arc::new(Mutex::new(HashSet::new())),
+            dependency_paths: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
-    /// Returns a copy of the paths to xml files as strings
-    fn xml_paths(&self) -> PyResult<Vec<String>> {
-        let xml_paths = self.xml_paths.lock().unwrap();
-        Ok(xml_paths
+    /// Returns a copy of the paths to the xml files and source files that have been read, as strings, so that the
+    /// documents can be updated when any of them change
+    fn dependency_paths(&self) -> PyResult<Vec<String>> {
+        let dependency_paths = self.dependency_paths.lock().unwrap();
+        Ok(dependency_paths
             .iter()
             .map(|path| path.display().to_string())
             .collect())
@@ -118,10 +134,19 @@ impl Cache for TrackedCache {
     }
 
     fn parse_compound(&self, xml_path: PathBuf) -> anyhow::Result<Arc<compound::DoxygenType>> {
-        let mut xml_paths = self.xml_paths.lock().unwrap();
-        xml_paths.insert(xml_path.clone());
+        let mut dependency_paths = self.dependency_paths.lock().unwrap();
+        dependency_paths.insert(xml_path.clone());
 
         let mut cache = self.inner.lock().unwrap();
         cache.parse_compound(xml_path)
     }
+
+    /// Tracks the source file so that the documents which include it are updated when it changes
+    fn read_source(&self, path: PathBuf) -> anyhow::Result<Arc<String>> {
+        let mut dependency_paths = self.dependency_paths.lock().unwrap();
+        dependency_paths.insert(path.clone());
+
+        let mut cache = self.inner.lock().unwrap();
+        cache.read_source(path)
+    }
 }
@@ -0,0 +1,526 @@
+//! Helper functions for converting the graphs in the doxygen xml into Graphviz DOT source so that they
+//! can be rendered by the 'sphinx.ext.graphviz' extension
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::doxygen::compound::generated as e;
+
+/// The direction in which the edges of the graph should point
+#[derive(Debug, Clone, Copy)]
+pub enum RankDir {
+    TopToBottom,
+    BottomToTop,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl RankDir {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::TopToBottom => "TB",
+            Self::BottomToTop => "BT",
+            Self::LeftToRight => "LR",
+            Self::RightToLeft => "RL",
+        }
+    }
+}
+
+/// Limits on the size of a graph, measured out from the root node, to keep large graphs readable
+#[derive(Debug, Clone, Default)]
+pub struct GraphLimits {
+    /// The maximum number of edges between the root node and any other node in the graph
+    pub max_depth: Option<usize>,
+    /// The maximum number of nodes in the graph
+    pub max_nodes: Option<usize>,
+}
+
+/// DOT source for a graph along with the refids used as placeholder urls for the links in the source
+pub struct Graph {
+    pub code: String,
+    pub refids: Vec<String>,
+}
+
+/// Renders the graph as DOT source. Nodes that link to documented entities are given a 'URL' attribute with the
+/// refid of the target as its value which needs to be resolved into a real url before the graph is rendered. The
+/// node which links to the 'root_refid' is highlighted as the subject of the graph.
+pub fn render_graph(
+    graph: &e::GraphType,
+    root_refid: &str,
+    rank_dir: RankDir,
+    limits: &GraphLimits,
+) -> Graph {
+    let nodes = select_nodes(graph, root_refid, limits);
+    let node_ids: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+
+    let mut lines = vec![
+        "digraph {".to_string(),
+        format!("    rankdir=\"{}\";", rank_dir.as_str()),
+        "    node [shape=\"box\", fontname=\"Helvetica\", fontsize=\"10\", height=\"0.2\"];"
+            .to_string(),
+        "    edge [fontname=\"Helvetica\", fontsize=\"10\"];".to_string(),
+    ];
+
+    for node in nodes.iter() {
+        // Mark nodes which have had some of their connections removed by the limits in the same way as Doxygen
+        let truncated = node
+            .childnode
+            .iter()
+            .any(|child_node| !node_ids.contains(child_node.refid.as_str()));
+        lines.push(render_node(node, root_refid, truncated));
+    }
+
+    for node in nodes.iter() {
+        for child_node in node.childnode.iter() {
+            if node_ids.contains(child_node.refid.as_str()) {
+                lines.push(render_edge(node, child_node));
+            }
+        }
+    }
+
+    lines.push("}".to_string());
+
+    Graph {
+        code: lines.join("\n"),
+        refids: nodes
+            .iter()
+            .filter_map(|node| internal_link(node))
+            .map(|link| link.refid.clone())
+            .collect(),
+    }
+}
+
+/// Builds a graph of the direct references to or from a member as the doxygen xml doesn't include call graphs. The
+/// edges point from the caller to the callee in both cases
+pub fn reference_graph(
+    root_refid: &str,
+    root_label: &str,
+    references: &[e::ReferenceType],
+    callers: bool,
+) -> e::GraphType {
+    let mut root = graph_node("1", root_label, root_refid);
+    let mut nodes = Vec::new();
+
+    for reference in references.iter() {
+        if reference.refid == root_refid
+            || nodes.iter().any(|node: &e::NodeType| {
+                internal_link(node).is_some_and(|link| link.refid == reference.refid)
+            })
+        {
+            continue;
+        }
+
+        let id = (nodes.len() + 2).to_string();
+        let mut node = graph_node(&id, &reference.content, &reference.refid);
+
+        // The graph relations don't include calls so we use 'include' as it is drawn as a plain solid edge
+        if callers {
+            node.childnode.push(call_edge("1"));
+        } else {
+            root.childnode.push(call_edge(&id));
+        }
+
+        nodes.push(node);
+    }
+
+    let mut graph = e::GraphType {
+        node: vec1::Vec1::new(root),
+    };
+    graph.node.extend(nodes);
+    graph
+}
+
+fn graph_node(id: &str, label: &str, refid: &str) -> e::NodeType {
+    e::NodeType {
+        id: id.to_string(),
+        label: label.to_string(),
+        link: Some(e::LinkType {
+            refid: refid.to_string(),
+            external: None,
+        }),
+        childnode: Vec::new(),
+    }
+}
+
+fn call_edge(refid: &str) -> e::ChildnodeType {
+    e::ChildnodeType {
+        refid: refid.to_string(),
+        relation: e::DoxGraphRelation::Include,
+        edgelabel: Vec::new(),
+    }
+}
+
+/// Walks the graph breadth first from the root node so that the nodes closest to the root are the ones that are kept
+/// when the graph is limited. Edges are followed in both directions as, for example, the derived classes in an
+/// inheritance graph point towards the root node rather than away from it
+fn select_nodes<'a>(
+    graph: &'a e::GraphType,
+    root_refid: &str,
+    limits: &GraphLimits,
+) -> Vec<&'a e::NodeType> {
+    let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in graph.node.iter() {
+        for child_node in node.childnode.iter() {
+            neighbours
+                .entry(node.id.as_str())
+                .or_default()
+                .push(child_node.refid.as_str());
+            neighbours
+                .entry(child_node.refid.as_str())
+                .or_default()
+                .push(node.id.as_str());
+        }
+    }
+
+    let nodes_by_id: HashMap<&str, &e::NodeType> = graph
+        .node
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    let root = graph
+        .node
+        .iter()
+        .find(|node| internal_link(node).is_some_and(|link| link.refid == root_refid))
+        .unwrap_or_else(|| graph.node.first());
+
+    let mut selected = Vec::new();
+    let mut seen = HashSet::from([root.id.as_str()]);
+    let mut queue = VecDeque::from([(root, 0)]);
+
+    while let Some((node, depth)) = queue.pop_front() {
+        if limits.max_nodes.is_some_and(|max| selected.len() >= max) {
+            break;
+        }
+
+        selected.push(node);
+
+        if limits.max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+
+        for id in neighbours.get(node.id.as_str()).into_iter().flatten() {
+            if seen.insert(id) {
+                if let Some(neighbour) = nodes_by_id.get(id) {
+                    queue.push_back((neighbour, depth + 1));
+                }
+            }
+        }
+    }
+
+    selected
+}
+
+fn render_node(node: &e::NodeType, root_refid: &str, truncated: bool) -> String {
+    let label = quote(&html_escape::decode_html_entities(&node.label));
+
+    let mut attributes = vec![format!("label={label}"), format!("tooltip={label}")];
+
+    if let Some(link) = internal_link(node) {
+        attributes.push(format!("URL={}", quote(&link.refid)));
+
+        if link.refid == root_refid {
+            attributes.push("style=\"filled\"".to_string());
+            attributes.push("fillcolor=\"grey75\"".to_string());
+        }
+    }
+
+    if truncated {
+        attributes.push("color=\"red\"".to_string());
+    }
+
+    format!("    {} [{}];", node_id(&node.id), attributes.join(", "))
+}
+
+fn render_edge(node: &e::NodeType, child_node: &e::ChildnodeType) -> String {
+    let (color, style) = match child_node.relation {
+        e::DoxGraphRelation::Include | e::DoxGraphRelation::PublicInheritance => {
+            ("midnightblue", "solid")
+        }
+        e::DoxGraphRelation::ProtectedInheritance => ("darkgreen", "solid"),
+        e::DoxGraphRelation::PrivateInheritance => ("firebrick4", "solid"),
+        e::DoxGraphRelation::Usage | e::DoxGraphRelation::TypeConstraint => {
+            ("darkorchid3", "dashed")
+        }
+        e::DoxGraphRelation::TemplateInstance => ("orange", "dashed"),
+    };
+
+    let mut attributes = vec![format!("color=\"{color}\""), format!("style=\"{style}\"")];
+
+    if !child_node.edgelabel.is_empty() {
+        let label = child_node
+            .edgelabel
+            .iter()
+            .map(|label| html_escape::decode_html_entities(label).into_owned())
+            .collect::<Vec<_>>()
+            .join("\n");
+        attributes.push(format!("label={}", quote(&label)));
+    }
+
+    format!(
+        "    {} -> {} [{}];",
+        node_id(&node.id),
+        node_id(&child_node.refid),
+        attributes.join(", ")
+    )
+}
+
+/// Links to external documentation, from tag files, have no target within the Sphinx project
+fn internal_link(node: &e::NodeType) -> Option<&e::LinkType> {
+    node.link.as_ref().filter(|link| link.external.is_none())
+}
+
+fn node_id(id: &str) -> String {
+    quote(&format!("node{id}"))
+}
+
+fn quote(str: &str) -> String {
+    format!(
+        "\"{}\"",
+        str.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn edge(refid: &str, relation: e::DoxGraphRelation, labels: &[&str]) -> e::ChildnodeType {
+        e::ChildnodeType {
+            refid: refid.to_string(),
+            relation,
+            edgelabel: labels.iter().map(|label| label.to_string()).collect(),
+        }
+    }
+
+    /// A collaboration graph for 'Derived' which inherits from 'Base' and has a member from an external library
+    fn collaboration_graph() -> e::GraphType {
+        let base = graph_node("1", "Base&lt;T&gt;", "class_base");
+
+        let mut string = graph_node("2", "std::string", "string");
+        string.link = Some(e::LinkType {
+            refid: "string".to_string(),
+            external: Some("cppreference.tag".to_string()),
+        });
+
+        let mut derived = graph_node("3", "Derived", "class_derived");
+        derived
+            .childnode
+            .push(edge("1", e::DoxGraphRelation::PublicInheritance, &[]));
+        derived.childnode.push(edge(
+            "2",
+            e::DoxGraphRelation::Usage,
+            &["name_", "\"label\""],
+        ));
+
+        e::GraphType {
+            node: vec1::Vec1::try_from_vec(vec![base, string, derived]).unwrap(),
+        }
+    }
+
+    /// A chain of nodes, '1' -> '2' -> '3' -> '4', along with '5' -> '1' which points towards the root like a derived
+    /// class in an inheritance graph. Each node links to 'refN'
+    fn chain_graph() -> e::GraphType {
+        let mut nodes: Vec<_> = (1..=5)
+            .map(|index| {
+                graph_node(
+                    &index.to_string(),
+                    &format!("Node{index}"),
+                    &format!("ref{index}"),
+                )
+            })
+            .collect();
+
+        nodes[0].childnode.push(call_edge("2"));
+        nodes[1].childnode.push(call_edge("3"));
+        nodes[2].childnode.push(call_edge("4"));
+        nodes[4].childnode.push(call_edge("1"));
+
+        e::GraphType {
+            node: vec1::Vec1::try_from_vec(nodes).unwrap(),
+        }
+    }
+
+    fn selected_ids(graph: &e::GraphType, root_refid: &str, limits: &GraphLimits) -> Vec<String> {
+        select_nodes(graph, root_refid, limits)
+            .into_iter()
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
+    fn references(names: &[&str]) -> Vec<e::ReferenceType> {
+        names
+            .iter()
+            .map(|name| e::ReferenceType {
+                refid: format!("ref_{name}"),
+                compoundref: None,
+                startline: 1,
+                endline: 1,
+                content: name.to_string(),
+            })
+            .collect()
+    }
+
+    /// The edges of the graph as pairs of labels
+    fn edges(graph: &e::GraphType) -> Vec<(String, String)> {
+        let labels: HashMap<_, _> = graph
+            .node
+            .iter()
+            .map(|node| (node.id.as_str(), node.label.clone()))
+            .collect();
+
+        graph
+            .node
+            .iter()
+            .flat_map(|node| {
+                node.childnode.iter().map(|child_node| {
+                    (
+                        node.label.clone(),
+                        labels[child_node.refid.as_str()].clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn render_graph_links_documented_nodes() {
+        let graph = render_graph(
+            &collaboration_graph(),
+            "class_derived",
+            RankDir::BottomToTop,
+            &GraphLimits::default(),
+        );
+
+        assert_eq!(graph.refids, ["class_derived", "class_base"]);
+    }
+
+    #[test]
+    fn render_graph_source() {
+        let graph = render_graph(
+            &collaboration_graph(),
+            "class_derived",
+            RankDir::BottomToTop,
+            &GraphLimits::default(),
+        );
+
+        assert_eq!(
+            graph.code.lines().collect::<Vec<_>>(),
+            [
+                "digraph {",
+                "    rankdir=\"BT\";",
+                "    node [shape=\"box\", fontname=\"Helvetica\", fontsize=\"10\", height=\"0.2\"];",
+                "    edge [fontname=\"Helvetica\", fontsize=\"10\"];",
+                "    \"node3\" [label=\"Derived\", tooltip=\"Derived\", URL=\"class_derived\", style=\"filled\", \
+                 fillcolor=\"grey75\"];",
+                "    \"node1\" [label=\"Base<T>\", tooltip=\"Base<T>\", URL=\"class_base\"];",
+                "    \"node2\" [label=\"std::string\", tooltip=\"std::string\"];",
+                "    \"node3\" -> \"node1\" [color=\"midnightblue\", style=\"solid\"];",
+                "    \"node3\" -> \"node2\" [color=\"darkorchid3\", style=\"dashed\", \
+                 label=\"name_\\n\\\"label\\\"\"];",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn select_nodes_without_limits() {
+        let graph = chain_graph();
+
+        assert_eq!(
+            selected_ids(&graph, "ref1", &GraphLimits::default()),
+            ["1", "2", "5", "3", "4"]
+        );
+    }
+
+    #[test]
+    fn select_nodes_starts_from_root() {
+        let graph = chain_graph();
+
+        assert_eq!(
+            selected_ids(&graph, "ref3", &GraphLimits::default()),
+            ["3", "2", "4", "1", "5"]
+        );
+    }
+
+    #[test]
+    fn select_nodes_falls_back_to_first_node() {
+        let graph = chain_graph();
+
+        assert_eq!(
+            selected_ids(&graph, "missing", &GraphLimits::default())[0],
+            "1"
+        );
+    }
+
+    #[test]
+    fn select_nodes_limited_by_depth() {
+        let graph = chain_graph();
+        let limits = GraphLimits {
+            max_depth: Some(1),
+            max_nodes: None,
+        };
+
+        assert_eq!(selected_ids(&graph, "ref1", &limits), ["1", "2", "5"]);
+    }
+
+    #[test]
+    fn select_nodes_limited_by_count() {
+        let graph = chain_graph();
+        let limits = GraphLimits {
+            max_depth: None,
+            max_nodes: Some(2),
+        };
+
+        assert_eq!(selected_ids(&graph, "ref1", &limits), ["1", "2"]);
+    }
+
+    #[test]
+    fn render_graph_marks_truncated_nodes() {
+        let limits = GraphLimits {
+            max_depth: Some(1),
+            max_nodes: None,
+        };
+        let graph = render_graph(&chain_graph(), "ref1", RankDir::TopToBottom, &limits);
+
+        let truncated: Vec<_> = graph
+            .code
+            .lines()
+            .filter(|line| line.contains("color=\"red\""))
+            .collect();
+        assert_eq!(
+            truncated,
+            ["    \"node2\" [label=\"Node2\", tooltip=\"Node2\", URL=\"ref2\", color=\"red\"];"]
+        );
+        assert_eq!(graph.refids, ["ref1", "ref2", "ref5"]);
+    }
+
+    #[test]
+    fn reference_graph_points_from_member_to_references() {
+        let graph = reference_graph("ref_f", "f", &references(&["g", "h", "g", "f"]), false);
+
+        assert_eq!(
+            edges(&graph),
+            [
+                ("f".to_string(), "g".to_string()),
+                ("f".to_string(), "h".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn reference_graph_points_from_callers_to_member() {
+        let graph = reference_graph("ref_f", "f", &references(&["g", "h"]), true);
+
+        assert_eq!(
+            edges(&graph),
+            [
+                ("g".to_string(), "f".to_string()),
+                ("h".to_string(), "f".to_string())
+            ]
+        );
+    }
+}
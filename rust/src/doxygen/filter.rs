@@ -0,0 +1,903 @@
+//! Rules for skipping parts of the doxygen xml when rendering. Each rule is made up of one or more conditions which
+//! must all match for the rule to apply, eg. 'members:undocumented & sections:public-func'. Conditions can be negated
+//! with a leading '!', eg. '!members:prot:public'.
+
+use std::str::FromStr;
+
+use crate::doxygen::compound::generated as e;
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum FilterError {
+    #[error("Unrecognised skip setting: {0}")]
+    Unrecognised(String),
+    #[error("Unrecognised {kind} '{value}' in skip setting: {setting}")]
+    UnrecognisedValue {
+        kind: &'static str,
+        value: String,
+        setting: String,
+    },
+    #[error("Invalid attribute selector '{selector}' in skip setting: {setting}")]
+    InvalidAttribute { selector: String, setting: String },
+    #[error("Invalid regex in skip setting '{setting}': {message}")]
+    InvalidRegex { setting: String, message: String },
+    #[error("Invalid glob pattern in skip setting '{setting}': {message}")]
+    InvalidPattern { setting: String, message: String },
+}
+
+/// The elements that are checked against the 'xml-nodes' conditions whilst rendering, in addition to those in the
+/// description command groups, along with the attributes that can be matched against for each element
+const FILTERABLE_ELEMENTS: &[(&str, &[&str])] = &[
+    ("compounddef", &["kind", "language", "prot"]),
+    ("sectiondef", &["kind"]),
+    ("memberdef", &["kind", "prot", "static", "virt"]),
+    ("simplesect", &["kind"]),
+    ("parameterlist", &["kind"]),
+    ("programlisting", &["filename"]),
+    ("image", &["type", "name"]),
+    ("heading", &["level"]),
+    ("xrefsect", &["id"]),
+];
+
+fn filterable_attributes(element: &str) -> Option<&'static [&'static str]> {
+    FILTERABLE_ELEMENTS
+        .iter()
+        .find(|(name, _)| *name == element)
+        .map(|(_, attributes)| *attributes)
+        .or_else(|| {
+            (e::DocCmdGroup::ELEMENT_NAMES.contains(&element)
+                || e::DocTitleCmdGroup::ELEMENT_NAMES.contains(&element))
+            .then_some(&[])
+        })
+}
+
+/// An element from the doxygen xml along with the attribute values that the conditions can match against
+pub struct XmlNode<'a> {
+    name: &'a str,
+    attributes: Vec<(&'static str, String)>,
+}
+
+impl<'a> XmlNode<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn with_attribute(mut self, name: &'static str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            self.attributes.push((name, value.to_string()));
+        }
+        self
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    /// Variables and functions with names which are all caps as they are often mishandled macros and shouldn't
+    /// really be included in the output as they will cause issues with the Sphinx domains
+    ///
+    /// TODO: We could explore rendering them but not sending them through the domain handling process as that is
+    /// the root of our issue
+    MemberAllCaps,
+    /// Members without a brief or detailed description
+    MemberUndocumented,
+    MemberProtection(e::DoxProtectionKind),
+    MemberKind(e::DoxMemberKind),
+    SectionKind(e::DoxSectionKind),
+    CompoundKind(e::DoxCompoundKind),
+    /// Members or compounds with names matching the regex
+    Name(regex::Regex),
+    /// Members or compounds declared in files with paths matching the pattern
+    File(glob::Pattern),
+    /// Doxygen xml element, optionally with specific attribute values, to ignore whilst rendering
+    XmlNode {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    Not(Box<Condition>),
+}
+
+/// The entity that we're deciding whether to skip along with the entities that contain it
+#[derive(Default)]
+struct Scope<'a> {
+    compound_def: Option<&'a e::CompounddefType>,
+    compound_kind: Option<&'a e::DoxCompoundKind>,
+    section_kind: Option<&'a e::DoxSectionKind>,
+    member_def: Option<&'a e::MemberdefType>,
+    xml_node: Option<XmlNode<'a>>,
+}
+
+impl<'a> Scope<'a> {
+    /// The name of the most specific entity in the scope
+    fn name(&self) -> Option<&str> {
+        self.member_def
+            .map(|member_def| member_def.name.as_str())
+            .or_else(|| {
+                self.compound_def
+                    .map(|compound_def| compound_def.compoundname.as_str())
+            })
+    }
+
+    /// The location of the most specific entity in the scope
+    fn location(&self) -> Option<&e::LocationType> {
+        match (self.member_def, self.compound_def) {
+            (Some(member_def), _) => member_def.location.as_ref(),
+            (None, Some(compound_def)) => compound_def.location.as_ref(),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Condition {
+    /// Returns None if the condition doesn't apply to the scope, eg. a member condition when considering a
+    /// whole section, so that negated conditions don't match everything outside of their intended scope
+    fn matches(&self, scope: &Scope) -> Option<bool> {
+        match self {
+            Self::MemberAllCaps => scope.member_def.map(|member_def| {
+                matches!(
+                    member_def.kind,
+                    e::DoxMemberKind::Variable | e::DoxMemberKind::Function
+                ) && is_upper_snake_case(&member_def.name)
+            }),
+            Self::MemberUndocumented => scope.member_def.map(|member_def| {
+                !has_content(member_def.briefdescription.as_ref())
+                    && !has_content(member_def.detaileddescription.as_ref())
+            }),
+            Self::MemberProtection(prot) => {
+                scope.member_def.map(|member_def| &member_def.prot == prot)
+            }
+            Self::MemberKind(kind) => scope.member_def.map(|member_def| &member_def.kind == kind),
+            Self::SectionKind(kind) => scope.section_kind.map(|section_kind| section_kind == kind),
+            Self::CompoundKind(kind) => scope
+                .compound_kind
+                .map(|compound_kind| compound_kind == kind),
+            Self::Name(regex) => scope.name().map(|name| regex.is_match(name)),
+            Self::File(pattern) => scope
+                .location()
+                .map(|location| pattern.matches(&location.file)),
+            Self::XmlNode { name, attributes } => scope.xml_node.as_ref().map(|xml_node| {
+                xml_node.name == name
+                    && attributes
+                        .iter()
+                        .all(|(attribute, value)| xml_node.attribute(attribute) == Some(value))
+            }),
+            Self::Not(condition) => condition.matches(scope).map(|matches| !matches),
+        }
+    }
+
+    /// Describes why the condition can never match anything, if that is the case
+    fn unused_reason(&self) -> Option<String> {
+        match self {
+            Self::XmlNode { name, attributes } => match filterable_attributes(name) {
+                None => Some(format!("'{name}' elements are not checked when rendering")),
+                Some(supported) => attributes
+                    .iter()
+                    .find(|(attribute, _)| !supported.contains(&attribute.as_str()))
+                    .map(|(attribute, _)| {
+                        format!("the '{attribute}' attribute is not checked for '{name}' elements")
+                    }),
+            },
+            Self::Not(condition) => condition.unused_reason(),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = FilterError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        if let Some(condition) = str.strip_prefix('!') {
+            return Ok(Self::Not(Box::new(condition.trim().parse()?)));
+        }
+
+        if str == "members:all_caps" {
+            return Ok(Self::MemberAllCaps);
+        }
+
+        if str == "members:undocumented" {
+            return Ok(Self::MemberUndocumented);
+        }
+
+        if let Some(prot) = str.strip_prefix("members:prot:") {
+            return parse_kind("protection", prot, str).map(Self::MemberProtection);
+        }
+
+        if let Some(kind) = str.strip_prefix("members:kind:") {
+            return parse_kind("member kind", kind, str).map(Self::MemberKind);
+        }
+
+        if let Some(kind) = str.strip_prefix("sections:") {
+            return parse_kind("section kind", kind, str).map(Self::SectionKind);
+        }
+
+        if let Some(kind) = str.strip_prefix("compounds:") {
+            return parse_kind("compound kind", kind, str).map(Self::CompoundKind);
+        }
+
+        if let Some(regex) = str.strip_prefix("names:") {
+            return regex::Regex::new(regex).map(Self::Name).map_err(|err| {
+                FilterError::InvalidRegex {
+                    setting: str.to_string(),
+                    message: err.to_string(),
+                }
+            });
+        }
+
+        if let Some(pattern) = str.strip_prefix("files:") {
+            return glob::Pattern::new(pattern).map(Self::File).map_err(|err| {
+                FilterError::InvalidPattern {
+                    setting: str.to_string(),
+                    message: err.msg.to_string(),
+                }
+            });
+        }
+
+        if let Some(node) = str.strip_prefix("xml-nodes:") {
+            return parse_xml_node(node, str);
+        }
+
+        Err(FilterError::Unrecognised(str.to_string()))
+    }
+}
+
+fn parse_kind<T: FromStr>(
+    kind: &'static str,
+    value: &str,
+    setting: &str,
+) -> Result<T, FilterError> {
+    value.parse().map_err(|_| FilterError::UnrecognisedValue {
+        kind,
+        value: value.to_string(),
+        setting: setting.to_string(),
+    })
+}
+
+/// Parses an element name with optional attribute selectors, eg. 'simplesect[kind=author]'
+fn parse_xml_node(node: &str, setting: &str) -> Result<Condition, FilterError> {
+    let (name, mut selectors) = match node.split_once('[') {
+        Some((name, selectors)) => (name, Some(selectors)),
+        None => (node, None),
+    };
+
+    let mut attributes = Vec::new();
+
+    while let Some(remaining) = selectors {
+        let invalid = || FilterError::InvalidAttribute {
+            selector: remaining.to_string(),
+            setting: setting.to_string(),
+        };
+
+        let (selector, rest) = remaining.split_once(']').ok_or_else(invalid)?;
+        let (attribute, value) = selector.split_once('=').ok_or_else(invalid)?;
+        attributes.push((attribute.trim().to_string(), value.trim().to_string()));
+
+        selectors = match rest.strip_prefix('[') {
+            Some(rest) => Some(rest),
+            None if rest.is_empty() => None,
+            None => return Err(invalid()),
+        };
+    }
+
+    Ok(Condition::XmlNode {
+        name: name.trim().to_string(),
+        attributes,
+    })
+}
+
+/// A set of conditions which must all match for the rule to apply
+#[derive(Debug, Clone)]
+struct Rule {
+    conditions: Vec<Condition>,
+}
+
+impl Rule {
+    fn matches(&self, scope: &Scope) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(scope) == Some(true))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = FilterError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let conditions = str
+            .split('&')
+            .map(|condition| condition.trim().parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { conditions })
+    }
+}
+
+/// The rules for skipping parts of the doxygen xml. Something is skipped if any of the rules match it
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    rules: Vec<Rule>,
+    /// Descriptions of the rules that can never match anything so that we can report them to the user
+    warnings: Vec<String>,
+}
+
+impl Filter {
+    pub fn from_settings(settings: &[String]) -> Result<Self, FilterError> {
+        let mut rules = Vec::new();
+        let mut warnings = Vec::new();
+
+        for setting in settings {
+            let rule: Rule = setting.parse()?;
+
+            for reason in rule.conditions.iter().filter_map(Condition::unused_reason) {
+                warnings.push(format!(
+                    "Skip setting '{setting}' will never match anything as {reason}"
+                ));
+            }
+
+            rules.push(rule);
+        }
+
+        Ok(Self { rules, warnings })
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn skip_compound(&self, compound_def: &e::CompounddefType) -> bool {
+        let xml_node = XmlNode::new("compounddef")
+            .with_attribute("kind", Some((&compound_def.kind).into()))
+            .with_attribute("language", compound_def.language.as_ref().map(Into::into))
+            .with_attribute("prot", compound_def.prot.as_ref().map(Into::into));
+
+        self.skip(&Scope {
+            compound_def: Some(compound_def),
+            compound_kind: Some(&compound_def.kind),
+            xml_node: Some(xml_node),
+            ..Scope::default()
+        })
+    }
+
+    pub fn skip_section(
+        &self,
+        compound_kind: &e::DoxCompoundKind,
+        section_def: &e::SectiondefType,
+    ) -> bool {
+        let xml_node =
+            XmlNode::new("sectiondef").with_attribute("kind", Some((&section_def.kind).into()));
+
+        self.skip(&Scope {
+            compound_kind: Some(compound_kind),
+            section_kind: Some(&section_def.kind),
+            xml_node: Some(xml_node),
+            ..Scope::default()
+        })
+    }
+
+    pub fn skip_member(
+        &self,
+        compound_kind: &e::DoxCompoundKind,
+        section_kind: &e::DoxSectionKind,
+        member_def: &e::MemberdefType,
+    ) -> bool {
+        let xml_node = XmlNode::new("memberdef")
+            .with_attribute("kind", Some((&member_def.kind).into()))
+            .with_attribute("prot", Some((&member_def.prot).into()))
+            .with_attribute("static", Some((&member_def.static_).into()))
+            .with_attribute("virt", member_def.virt.as_ref().map(Into::into));
+
+        self.skip(&Scope {
+            compound_kind: Some(compound_kind),
+            section_kind: Some(section_kind),
+            member_def: Some(member_def),
+            xml_node: Some(xml_node),
+            ..Scope::default()
+        })
+    }
+
+    pub fn skip_doc_cmd(&self, doc_cmd_group: &e::DocCmdGroup) -> bool {
+        let xml_node = XmlNode::new(doc_cmd_group.element_name());
+
+        let xml_node = match doc_cmd_group {
+            e::DocCmdGroup::Simplesect(element) => {
+                xml_node.with_attribute("kind", Some((&element.kind).into()))
+            }
+            e::DocCmdGroup::Parameterlist(element) => {
+                xml_node.with_attribute("kind", Some((&element.kind).into()))
+            }
+            e::DocCmdGroup::Programlisting(element) => {
+                xml_node.with_attribute("filename", element.filename.as_deref())
+            }
+            e::DocCmdGroup::Heading(element) => {
+                xml_node.with_attribute("level", Some(&element.level.to_string()))
+            }
+            e::DocCmdGroup::Xrefsect(element) => xml_node.with_attribute("id", Some(&element.id)),
+            _ => xml_node,
+        };
+
+        self.skip(&Scope {
+            xml_node: Some(xml_node),
+            ..Scope::default()
+        })
+    }
+
+    pub fn skip_doc_title_cmd(&self, doc_title_cmd_group: &e::DocTitleCmdGroup) -> bool {
+        let xml_node = XmlNode::new(doc_title_cmd_group.element_name());
+
+        let xml_node = match doc_title_cmd_group {
+            e::DocTitleCmdGroup::Image(element) => xml_node
+                .with_attribute("type", element.type_.as_ref().map(Into::into))
+                .with_attribute("name", element.name.as_deref()),
+            _ => xml_node,
+        };
+
+        self.skip(&Scope {
+            xml_node: Some(xml_node),
+            ..Scope::default()
+        })
+    }
+
+    fn skip(&self, scope: &Scope) -> bool {
+        self.rules.iter().any(|rule| rule.matches(scope))
+    }
+}
+
+fn is_upper_snake_case(str: &str) -> bool {
+    str.chars()
+        .all(|char| char.is_ascii_uppercase() || char == '_')
+}
+
+fn has_content(description: Option<&e::DescriptionType>) -> bool {
+    description.is_some_and(|description| {
+        !description.para.is_empty()
+            || !description.sect1.is_empty()
+            || !description.internal.is_empty()
+    })
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SelectionError {
+    #[error("Invalid member pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+    #[error("Unrecognised section kind: {0}")]
+    UnrecognisedSection(String),
+}
+
+/// The members and sections of a compound that have been requested for a particular directive
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// If provided, only members with names matching one of these patterns are included
+    members: Option<Vec<glob::Pattern>>,
+    /// Members with names matching any of these patterns are excluded
+    exclude_members: Vec<glob::Pattern>,
+    /// If provided, only sections of these kinds are included
+    sections: Option<Vec<e::DoxSectionKind>>,
+}
+
+impl Selection {
+    pub fn new(
+        members: Option<Vec<String>>,
+        sections: Option<Vec<String>>,
+        exclude_members: Vec<String>,
+    ) -> Result<Self, SelectionError> {
+        let members = members
+            .map(|members| parse_patterns(&members))
+            .transpose()?;
+
+        let exclude_members = parse_patterns(&exclude_members)?;
+
+        let sections = sections
+            .map(|sections| {
+                sections
+                    .iter()
+                    .map(|section| {
+                        section
+                            .parse()
+                            .map_err(|_| SelectionError::UnrecognisedSection(section.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            members,
+            exclude_members,
+            sections,
+        })
+    }
+
+    pub fn includes_section(&self, section_def: &e::SectiondefType) -> bool {
+        match self.sections {
+            Some(ref sections) => sections.contains(&section_def.kind),
+            None => true,
+        }
+    }
+
+    /// Checks the member name and, for nested entities, the fully qualified name against the patterns
+    pub fn includes_member(&self, names: &[&str]) -> bool {
+        let matches = |pattern: &glob::Pattern| names.iter().any(|name| pattern.matches(name));
+
+        let included = match self.members {
+            Some(ref members) => members.iter().any(matches),
+            None => true,
+        };
+
+        included && !self.exclude_members.iter().any(matches)
+    }
+}
+
+fn parse_patterns(patterns: &[String]) -> Result<Vec<glob::Pattern>, SelectionError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern).map_err(|err| SelectionError::InvalidPattern {
+                pattern: pattern.clone(),
+                message: err.msg.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn parse(str: &str) -> Condition {
+        str.parse().unwrap()
+    }
+
+    /// Parses the memberdef elements from a class compound
+    fn parse_member_defs(members: &str) -> Vec<e::MemberdefType> {
+        let xml = format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="class_a" kind="class" language="C++" prot="public">
+                    <compoundname>A</compoundname>
+                    <sectiondef kind="public-func">{members}</sectiondef>
+                </compounddef>
+            </doxygen>"#
+        );
+
+        let root = e::parse(&xml).unwrap();
+        root.compounddef
+            .unwrap()
+            .sectiondef
+            .remove(0)
+            .memberdef
+            .into_vec()
+    }
+
+    /// The names of the members which aren't skipped by the filter
+    fn kept_members(
+        settings: &[&str],
+        section_kind: e::DoxSectionKind,
+        member_defs: &[e::MemberdefType],
+    ) -> Vec<String> {
+        let settings: Vec<_> = settings.iter().map(|setting| setting.to_string()).collect();
+        let filter = Filter::from_settings(&settings).unwrap();
+
+        member_defs
+            .iter()
+            .filter(|member_def| {
+                !filter.skip_member(&e::DoxCompoundKind::Class, &section_kind, member_def)
+            })
+            .map(|member_def| member_def.name.clone())
+            .collect()
+    }
+
+    fn example_members() -> Vec<e::MemberdefType> {
+        parse_member_defs(
+            r#"<memberdef kind="function" id="class_a_1a" prot="public" static="no">
+                <name>documented</name>
+                <briefdescription><para>Brief</para></briefdescription>
+            </memberdef>
+            <memberdef kind="function" id="class_a_1b" prot="public" static="no">
+                <name>undocumented</name>
+            </memberdef>
+            <memberdef kind="function" id="class_a_1c" prot="private" static="no">
+                <name>hidden</name>
+                <briefdescription><para>Brief</para></briefdescription>
+            </memberdef>
+            <memberdef kind="variable" id="class_a_1d" prot="public" static="no">
+                <name>MAX_SIZE</name>
+            </memberdef>"#,
+        )
+    }
+
+    #[test]
+    fn parse_member_conditions() {
+        assert!(matches!(
+            parse("members:all_caps"),
+            Condition::MemberAllCaps
+        ));
+        assert!(matches!(
+            parse("members:undocumented"),
+            Condition::MemberUndocumented
+        ));
+        assert!(matches!(
+            parse("members:prot:private"),
+            Condition::MemberProtection(e::DoxProtectionKind::Private)
+        ));
+    }
+
+    #[test]
+    fn parse_kind_conditions() {
+        assert!(matches!(
+            parse("sections:public-func"),
+            Condition::SectionKind(e::DoxSectionKind::PublicFunc)
+        ));
+        assert!(matches!(
+            parse("compounds:namespace"),
+            Condition::CompoundKind(e::DoxCompoundKind::Namespace)
+        ));
+    }
+
+    #[test]
+    fn parse_negated_condition() {
+        match parse("! members:prot:public") {
+            Condition::Not(condition) => assert!(matches!(
+                *condition,
+                Condition::MemberProtection(e::DoxProtectionKind::Public)
+            )),
+            condition => panic!("Unexpected condition: {condition:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_unrecognised_conditions() {
+        assert!(matches!(
+            "members:everything".parse::<Condition>(),
+            Err(FilterError::Unrecognised(_))
+        ));
+        assert!(matches!(
+            "members:prot:secret".parse::<Condition>(),
+            Err(FilterError::UnrecognisedValue {
+                kind: "protection",
+                ..
+            })
+        ));
+        assert!(matches!(
+            "sections:everything".parse::<Condition>(),
+            Err(FilterError::UnrecognisedValue {
+                kind: "section kind",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_rule_with_several_conditions() {
+        let rule: Rule = "members:undocumented & !sections:public-func"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.conditions.len(), 2);
+    }
+
+    #[test]
+    fn skip_members_by_protection_and_documentation() {
+        let members = example_members();
+
+        assert_eq!(
+            kept_members(
+                &["members:prot:private"],
+                e::DoxSectionKind::PublicFunc,
+                &members
+            ),
+            ["documented", "undocumented", "MAX_SIZE"]
+        );
+        assert_eq!(
+            kept_members(
+                &["members:undocumented", "members:all_caps"],
+                e::DoxSectionKind::PublicFunc,
+                &members
+            ),
+            ["documented", "hidden"]
+        );
+    }
+
+    #[test]
+    fn skip_members_when_all_conditions_match() {
+        let members = example_members();
+        let settings = ["members:undocumented & !sections:public-func"];
+
+        assert_eq!(
+            kept_members(&settings, e::DoxSectionKind::PublicFunc, &members),
+            ["documented", "undocumented", "hidden", "MAX_SIZE"]
+        );
+        assert_eq!(
+            kept_members(&settings, e::DoxSectionKind::PublicAttrib, &members),
+            ["documented", "hidden"]
+        );
+    }
+
+    #[test]
+    fn negated_member_conditions_do_not_match_sections() {
+        let filter = Filter::from_settings(&["!members:prot:public".to_string()]).unwrap();
+        let xml = r#"<doxygen version="1.9.7">
+            <compounddef id="class_a" kind="class" language="C++" prot="public">
+                <compoundname>A</compoundname>
+                <sectiondef kind="public-func">
+                    <memberdef kind="function" id="class_a_1a" prot="private" static="no">
+                        <name>f</name>
+                    </memberdef>
+                </sectiondef>
+            </compounddef>
+        </doxygen>"#;
+        let compound_def = e::parse(xml).unwrap().compounddef.unwrap();
+
+        assert!(!filter.skip_compound(&compound_def));
+        let section_def = &compound_def.sectiondef[0];
+        assert!(!filter.skip_section(&compound_def.kind, section_def));
+        assert!(filter.skip_member(
+            &compound_def.kind,
+            &section_def.kind,
+            section_def.memberdef.first()
+        ));
+    }
+
+    #[test]
+    fn parse_member_kind_condition() {
+        assert!(matches!(
+            parse("members:kind:function"),
+            Condition::MemberKind(e::DoxMemberKind::Function)
+        ));
+    }
+
+    #[test]
+    fn parse_name_and_file_conditions() {
+        match parse("names:^detail_") {
+            Condition::Name(regex) => assert!(regex.is_match("detail_impl")),
+            condition => panic!("Unexpected condition: {condition:?}"),
+        }
+
+        match parse("files:src/internal/*") {
+            Condition::File(pattern) => assert!(pattern.matches("src/internal/impl.h")),
+            condition => panic!("Unexpected condition: {condition:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_xml_node_conditions() {
+        match parse("xml-nodes:simplesect[kind=author][ id = a ]") {
+            Condition::XmlNode { name, attributes } => {
+                assert_eq!(name, "simplesect");
+                assert_eq!(
+                    attributes,
+                    [
+                        ("kind".to_string(), "author".to_string()),
+                        ("id".to_string(), "a".to_string())
+                    ]
+                );
+            }
+            condition => panic!("Unexpected condition: {condition:?}"),
+        }
+
+        match parse("xml-nodes:programlisting") {
+            Condition::XmlNode { name, attributes } => {
+                assert_eq!(name, "programlisting");
+                assert!(attributes.is_empty());
+            }
+            condition => panic!("Unexpected condition: {condition:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_invalid_conditions() {
+        assert!(matches!(
+            "names:(".parse::<Condition>(),
+            Err(FilterError::InvalidRegex { .. })
+        ));
+        assert!(matches!(
+            "files:[".parse::<Condition>(),
+            Err(FilterError::InvalidPattern { .. })
+        ));
+        assert!(matches!(
+            "xml-nodes:simplesect[kind]".parse::<Condition>(),
+            Err(FilterError::InvalidAttribute { .. })
+        ));
+        assert!(matches!(
+            "xml-nodes:simplesect[kind=author]x".parse::<Condition>(),
+            Err(FilterError::InvalidAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn skip_members_by_kind_name_and_file() {
+        let members = parse_member_defs(
+            r#"<memberdef kind="function" id="class_a_1a" prot="public" static="no">
+                <name>run</name>
+                <location file="src/api.h" line="1"/>
+            </memberdef>
+            <memberdef kind="function" id="class_a_1b" prot="public" static="no">
+                <name>detail_run</name>
+                <location file="src/api.h" line="2"/>
+            </memberdef>
+            <memberdef kind="function" id="class_a_1c" prot="public" static="no">
+                <name>helper</name>
+                <location file="src/internal/impl.h" line="1"/>
+            </memberdef>
+            <memberdef kind="typedef" id="class_a_1d" prot="public" static="no">
+                <name>size_type</name>
+                <location file="src/api.h" line="3"/>
+            </memberdef>"#,
+        );
+
+        assert_eq!(
+            kept_members(
+                &["members:kind:typedef"],
+                e::DoxSectionKind::PublicFunc,
+                &members
+            ),
+            ["run", "detail_run", "helper"]
+        );
+        assert_eq!(
+            kept_members(
+                &["names:^detail_", "files:src/internal/*"],
+                e::DoxSectionKind::PublicFunc,
+                &members
+            ),
+            ["run", "size_type"]
+        );
+    }
+
+    #[test]
+    fn skip_doc_commands_by_attribute() {
+        let filter =
+            Filter::from_settings(&["xml-nodes:simplesect[kind=author]".to_string()]).unwrap();
+        let xml = r#"<doxygen version="1.9.7">
+            <compounddef id="class_a" kind="class" language="C++" prot="public">
+                <compoundname>A</compoundname>
+                <detaileddescription>
+                    <para><simplesect kind="author"><para>Me</para></simplesect></para>
+                    <para><simplesect kind="note"><para>Note</para></simplesect></para>
+                </detaileddescription>
+            </compounddef>
+        </doxygen>"#;
+        let compound_def = e::parse(xml).unwrap().compounddef.unwrap();
+
+        let skipped: Vec<_> = compound_def
+            .detaileddescription
+            .unwrap()
+            .para
+            .iter()
+            .flat_map(|para| para.content.iter())
+            .filter_map(|item| match item {
+                e::DocParaTypeItem::DocCmdGroup(doc_cmd_group) => {
+                    Some(filter.skip_doc_cmd(doc_cmd_group))
+                }
+                e::DocParaTypeItem::Text(_) => None,
+            })
+            .collect();
+
+        assert_eq!(skipped, [true, false]);
+    }
+
+    #[test]
+    fn warnings_for_conditions_that_never_match() {
+        let filter = Filter::from_settings(&[
+            "xml-nodes:simplesect[kind=author]".to_string(),
+            "xml-nodes:unknown".to_string(),
+            "xml-nodes:sectiondef[prot=public]".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            filter.warnings(),
+            [
+                "Skip setting 'xml-nodes:unknown' will never match anything as 'unknown' elements are not checked \
+                 when rendering",
+                "Skip setting 'xml-nodes:sectiondef[prot=public]' will never match anything as the 'prot' attribute \
+                 is not checked for 'sectiondef' elements"
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
 pub mod compound;
+pub mod dot;
+pub mod filter;
 pub mod index;
 pub mod nodes;
 pub mod render;
@@ -5,8 +5,6 @@ use std::{
 
 use pyo3::prelude::*;
 
-use crate::doxygen::compound::generated as e;
-
 #[derive(Clone)]
 pub enum CallAs {
     Element,
@@ -135,6 +133,7 @@ impl IntoPy<PyObject> for Target {
 #[derive(Debug, Clone)]
 pub enum DomainEntryType {
     Class,
+    Concept,
     Define,
     Enum,
     Enumerator,
@@ -149,6 +148,7 @@ impl IntoPy<PyObject> for DomainEntryType {
     fn into_py(self, py: Python<'_>) -> PyObject {
         match self {
             Self::Class => "class".into_py(py),
+            Self::Concept => "concept".into_py(py),
             Self::Define => "define".into_py(py),
             Self::Enum => "enum".into_py(py),
             Self::Enumerator => "enumerator".into_py(py),
@@ -168,14 +168,11 @@ pub struct Location {
 }
 
 impl Location {
-    pub fn from(root: &Path, location_type: &e::LocationType) -> Option<Self> {
-        location_type.line.map(|line| Self {
-            path: root
-                .join(PathBuf::from(&location_type.file))
-                .display()
-                .to_string(),
+    pub fn new(root: &Path, file: &str, line: i32) -> Self {
+        Self {
+            path: root.join(PathBuf::from(file)).display().to_string(),
             line,
-        })
+        }
     }
 }
 
@@ -208,6 +205,9 @@ pub enum Node {
     DomainEntry(Box<DomainEntry>),
 
     // Nodes
+    BlockQuote(Vec<Node>),
+    /// Groups multiple block level nodes into a single logical paragraph
+    Compound(Vec<Node>),
     /// Used in this code base like an html5 div - just a block level wrapper
     Container(Vec<Node>),
     /// sphinx.addnodes.desc requires 'objtype' and 'domain' attributes
@@ -227,11 +227,24 @@ pub enum Node {
     DescSignatureName(String),
     // DescSignaturePunctuation(String),
     DescSignatureSpace,
+    /// Collapsible block matching the html5 details element. The first child might be a DetailsSummary
+    Details(Vec<Node>),
+    DetailsSummary(Vec<Node>),
     Emphasis(Vec<Node>),
     // Index(Vec<IndexEntry>),
     HtmlOnly(Vec<Node>),
     Literal(Vec<Node>),
     LiteralBlock(Vec<Node>),
+    /// Source code to be highlighted in the given language. Sphinx can only highlight blocks which contain plain text
+    /// so blocks with references in them are rendered without highlighting
+    CodeBlock {
+        language: Option<String>,
+        /// The number of the first line when line numbers are to be shown
+        line_number_start: Option<i32>,
+        /// The lines, counting from 1 at the start of the block, to emphasise
+        highlighted_lines: Vec<usize>,
+        nodes: Vec<Node>,
+    },
     LiteralStrong(Vec<Node>),
     Paragraph(Vec<Node>),
     RawHtml(String),
@@ -239,6 +252,13 @@ pub enum Node {
         classes: Vec<String>,
         nodes: Vec<Node>,
     },
+    /// A titled section, as used for Doxygen pages and their sections, with the Doxygen id as the target so that
+    /// references to the section resolve
+    Section {
+        target: Target,
+        title: Vec<Node>,
+        nodes: Vec<Node>,
+    },
     Strong(Vec<Node>),
     Target(Target),
 
@@ -259,11 +279,26 @@ pub enum Node {
         nodes: Vec<Node>,
     },
 
+    // Graphs
+    /// Graphviz DOT source for rendering with 'sphinx.ext.graphviz'. The refids are the targets of the links in the
+    /// graph which need resolving to urls once we know where the targets have been written
+    Graphviz {
+        code: String,
+        alt: String,
+        refids: Vec<String>,
+    },
+
     // References
     InternalReference {
         refid: String,
         children: Vec<Node>,
     },
+    /// A reference to a target which might not be included in the Sphinx documentation, in which case the children
+    /// are shown as plain text rather than as a broken reference
+    OptionalReference {
+        refid: String,
+        children: Vec<Node>,
+    },
     ExternalReference {
         refuri: String,
         children: Vec<Node>,
@@ -275,6 +310,12 @@ pub enum Node {
     FieldName(Vec<Node>),
     FieldBody(Vec<Node>),
 
+    // Definition lists
+    DefinitionList(Vec<Node>),
+    DefinitionListItem(Vec<Node>),
+    Term(Vec<Node>),
+    Definition(Vec<Node>),
+
     // Lists
     BulletList(Vec<Node>),
     EnumeratedList {
@@ -284,6 +325,12 @@ pub enum Node {
     ListItem(Vec<Node>),
 
     // Notes
+    /// Generic admonition with a custom title
+    Admonition {
+        title: Vec<Node>,
+        nodes: Vec<Node>,
+    },
+    Attention(Vec<Node>),
     Note(Vec<Node>),
     SeeAlso(Vec<Node>),
     Warning(Vec<Node>),
@@ -351,6 +398,12 @@ impl IntoPy<PyObject> for Node {
             Self::Strong(nodes) => {
                 node(py, "strong", CallAs::TextElement, Attributes::new(), nodes).into_py(py)
             }
+            Self::BlockQuote(nodes) => {
+                node(py, "block_quote", CallAs::Element, Attributes::new(), nodes).into_py(py)
+            }
+            Self::Compound(nodes) => {
+                node(py, "compound", CallAs::Element, Attributes::new(), nodes).into_py(py)
+            }
             Self::Container(nodes) => {
                 node(py, "container", CallAs::Element, Attributes::new(), nodes).into_py(py)
             }
@@ -458,6 +511,17 @@ impl IntoPy<PyObject> for Node {
                 vec![text(" ".to_string())],
             )
             .into_py(py),
+            Self::Details(nodes) => {
+                node(py, "details", CallAs::Element, Attributes::new(), nodes).into_py(py)
+            }
+            Self::DetailsSummary(nodes) => node(
+                py,
+                "details_summary",
+                CallAs::TextElement,
+                Attributes::new(),
+                nodes,
+            )
+            .into_py(py),
             Self::Emphasis(nodes) => node(
                 py,
                 "emphasis",
@@ -495,6 +559,23 @@ impl IntoPy<PyObject> for Node {
                 nodes,
             )
             .into_py(py),
+            Self::CodeBlock {
+                language,
+                line_number_start,
+                highlighted_lines,
+                nodes,
+            } => node(
+                py,
+                "code_block",
+                CallAs::Function,
+                Attributes::from([
+                    ("language".into(), language.into_py(py)),
+                    ("line_number_start".into(), line_number_start.into_py(py)),
+                    ("highlighted_lines".into(), highlighted_lines.into_py(py)),
+                ]),
+                nodes,
+            )
+            .into_py(py),
             Self::LiteralStrong(nodes) => node(
                 py,
                 "literal_strong",
@@ -519,6 +600,18 @@ impl IntoPy<PyObject> for Node {
                 vec![text(content).into_py(py)],
             )
             .into_py(py),
+            Self::Graphviz { code, alt, refids } => node(
+                py,
+                "graphviz",
+                CallAs::Function,
+                Attributes::from([
+                    ("code".into(), code.into_py(py)),
+                    ("alt".into(), alt.into_py(py)),
+                    ("refids".into(), refids.into_py(py)),
+                ]),
+                Vec::<Node>::new(),
+            )
+            .into_py(py),
             Self::InternalReference { refid, children } => node(
                 py,
                 "internal_reference",
@@ -527,6 +620,14 @@ impl IntoPy<PyObject> for Node {
                 children,
             )
             .into_py(py),
+            Self::OptionalReference { refid, children } => node(
+                py,
+                "optional_reference",
+                CallAs::Function,
+                Attributes::from([("refid".into(), refid.into_py(py))]),
+                children,
+            )
+            .into_py(py),
             Self::ExternalReference { refuri, children } => node(
                 py,
                 "external_reference",
@@ -543,6 +644,27 @@ impl IntoPy<PyObject> for Node {
                 nodes,
             )
             .into_py(py),
+            Self::Section {
+                target,
+                title,
+                nodes,
+            } => {
+                let mut children =
+                    vec![
+                        node(py, "title", CallAs::TextElement, Attributes::new(), title)
+                            .into_py(py),
+                    ];
+                children.extend(nodes.into_iter().map(|entry| entry.into_py(py)));
+
+                node(
+                    py,
+                    "section",
+                    CallAs::Function,
+                    Attributes::from([("target".into(), target.into_py(py))]),
+                    children,
+                )
+                .into_py(py)
+            }
             Self::Target(target) => node(
                 py,
                 "target",
@@ -626,6 +748,30 @@ impl IntoPy<PyObject> for Node {
                 node(py, "field_body", CallAs::Element, Attributes::new(), nodes).into_py(py)
             }
 
+            // Definition lists
+            Self::DefinitionList(nodes) => node(
+                py,
+                "definition_list",
+                CallAs::Element,
+                Attributes::new(),
+                nodes,
+            )
+            .into_py(py),
+            Self::DefinitionListItem(nodes) => node(
+                py,
+                "definition_list_item",
+                CallAs::Element,
+                Attributes::new(),
+                nodes,
+            )
+            .into_py(py),
+            Self::Term(nodes) => {
+                node(py, "term", CallAs::TextElement, Attributes::new(), nodes).into_py(py)
+            }
+            Self::Definition(nodes) => {
+                node(py, "definition", CallAs::Element, Attributes::new(), nodes).into_py(py)
+            }
+
             // Lists
             Self::BulletList(nodes) => {
                 node(py, "bullet_list", CallAs::Element, Attributes::new(), nodes).into_py(py)
@@ -643,6 +789,26 @@ impl IntoPy<PyObject> for Node {
             }
 
             // Notes
+            Self::Admonition { title, nodes } => {
+                let mut children =
+                    vec![
+                        node(py, "title", CallAs::TextElement, Attributes::new(), title)
+                            .into_py(py),
+                    ];
+                children.extend(nodes.into_iter().map(|entry| entry.into_py(py)));
+
+                node(
+                    py,
+                    "admonition",
+                    CallAs::Element,
+                    Attributes::new(),
+                    children,
+                )
+                .into_py(py)
+            }
+            Self::Attention(nodes) => {
+                node(py, "attention", CallAs::Element, Attributes::new(), nodes).into_py(py)
+            }
             Self::Note(nodes) => {
                 node(py, "note", CallAs::Element, Attributes::new(), nodes).into_py(py)
             }
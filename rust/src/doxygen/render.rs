@@ -1,24 +1,18 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
 use crate::doxygen::compound::generated as e;
 use crate::doxygen::compound::CompoundDefEntry;
+use crate::doxygen::dot::{self, GraphLimits, RankDir};
+use crate::doxygen::filter::{Filter, Selection};
+use crate::doxygen::index::generated as index;
 use crate::doxygen::nodes::{
     Domain, DomainEntry, DomainEntryType, ListEnumType, Location, Node, SignatureType, Target,
 };
 use crate::doxygen::text;
 use crate::XmlLoader;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Skip {
-    /// Ignore any members which all caps names as in some situations it just means we haven't handled some
-    /// macros properly and we'd rather ignore them
-    MembersAllCaps,
-    /// Doxygen xml node to ignore whilst rendering - limited support
-    XmlNode(String),
-}
-
 fn domain_from_language(language: &e::DoxLanguage) -> Option<Domain> {
     match language {
         e::DoxLanguage::CPlusPlus => Some(Domain::CPlusPlus),
@@ -26,6 +20,93 @@ fn domain_from_language(language: &e::DoxLanguage) -> Option<Domain> {
     }
 }
 
+/// The name of the Pygments lexer for the language, if it is one that Pygments supports
+fn lexer_from_language(language: &e::DoxLanguage) -> Option<&'static str> {
+    match language {
+        e::DoxLanguage::Idl => Some("idl"),
+        e::DoxLanguage::Java => Some("java"),
+        e::DoxLanguage::CSharp => Some("csharp"),
+        e::DoxLanguage::D => Some("d"),
+        e::DoxLanguage::Php => Some("php"),
+        e::DoxLanguage::ObjectiveC => Some("objective-c"),
+        e::DoxLanguage::CPlusPlus => Some("cpp"),
+        e::DoxLanguage::JavaScript => Some("javascript"),
+        e::DoxLanguage::Python => Some("python"),
+        e::DoxLanguage::Fortran => Some("fortran"),
+        e::DoxLanguage::Vhdl => Some("vhdl"),
+        e::DoxLanguage::Xml => Some("xml"),
+        e::DoxLanguage::Sql => Some("sql"),
+        e::DoxLanguage::Markdown => Some("markdown"),
+        e::DoxLanguage::Slice | e::DoxLanguage::Lex | e::DoxLanguage::Unknown => None,
+    }
+}
+
+/// The name of the Pygments lexer for files with the extension, following the extensions that Doxygen recognises
+fn lexer_from_extension(extension: &str) -> Option<&'static str> {
+    match extension {
+        "c" => Some("c"),
+        "cc" | "cxx" | "cpp" | "c++" | "h" | "hh" | "hxx" | "hpp" | "h++" | "ipp" | "inl"
+        | "tcc" => Some("cpp"),
+        "idl" | "ddl" | "odl" => Some("idl"),
+        "java" => Some("java"),
+        "cs" => Some("csharp"),
+        "d" => Some("d"),
+        "php" | "php4" | "php5" | "inc" | "phtml" => Some("php"),
+        "m" | "mm" => Some("objective-c"),
+        "js" => Some("javascript"),
+        "py" | "pyw" => Some("python"),
+        "f" | "for" | "f90" | "f95" | "f03" | "f08" | "f18" => Some("fortran"),
+        "vhd" | "vhdl" => Some("vhdl"),
+        "xml" | "xsd" => Some("xml"),
+        "sql" => Some("sql"),
+        "md" | "markdown" => Some("markdown"),
+        "sh" | "bash" => Some("bash"),
+        "cmake" => Some("cmake"),
+        "json" => Some("json"),
+        "rs" => Some("rust"),
+        "txt" => Some("text"),
+        _ => None,
+    }
+}
+
+fn lexer_from_domain(domain: &Domain) -> &'static str {
+    match domain {
+        Domain::CPlusPlus => "cpp",
+        Domain::C => "c",
+    }
+}
+
+/// Determines the language to highlight a listing with, preferring the extension of the listing's file name, which
+/// is all that Doxygen provides for '\code{.py}' blocks. Otherwise we fall back to the current domain, as Doxygen
+/// reports C code as C++, and then to the language of the compound for languages without a domain
+fn listing_language(ctx: &Context, filename: Option<&str>) -> Option<String> {
+    listing_extension(filename)
+        .and_then(|extension| {
+            ctx.extension_domain_lookup
+                .get(extension)
+                .map(lexer_from_domain)
+                .or_else(|| lexer_from_extension(&extension.to_lowercase()))
+        })
+        .or_else(|| ctx.domain.as_ref().map(lexer_from_domain))
+        .or(ctx.language)
+        .map(String::from)
+}
+
+/// Doxygen gives '\code{.py}' blocks a file name of just the extension, eg. '.py', which has no extension as far as
+/// 'Path' is concerned so we handle that form before looking at the extension of a full file name
+fn listing_extension(filename: Option<&str>) -> Option<&str> {
+    let filename = filename?;
+
+    filename
+        .strip_prefix('.')
+        .filter(|extension| !extension.contains('.'))
+        .or_else(|| {
+            std::path::Path::new(filename)
+                .extension()
+                .and_then(|extension| extension.to_str())
+        })
+}
+
 fn domain_from_location(ctx: &Context, location: &e::LocationType) -> Option<Domain> {
     let extension = std::path::Path::new(&location.file).extension();
     if let Some(extension) = extension.and_then(|str| str.to_str()) {
@@ -35,15 +116,295 @@ fn domain_from_location(ctx: &Context, location: &e::LocationType) -> Option<Dom
     }
 }
 
+/// How to present the entries in the field list for function parameters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ParameterFormat {
+    /// Only the parameter names, without directions or types
+    #[default]
+    Names,
+    /// Doxygen style with the direction before the name and the type after it, eg. '[in] name (type)'
+    Annotated,
+    /// Matches the output of Sphinx's ':param type name:' fields, eg. 'name (type, in)'
+    Sphinx,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ParameterFormatError {
+    #[error("Unrecognised parameter format: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for ParameterFormat {
+    type Err = ParameterFormatError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "names" => Ok(Self::Names),
+            "annotated" => Ok(Self::Annotated),
+            "sphinx" => Ok(Self::Sphinx),
+            _ => Err(ParameterFormatError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// How to order the sections of a compound and the members within each section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SortStrategy {
+    /// Sections in a fixed order by kind, eg. types before functions, and members in the order they appear in the
+    /// Doxygen XML
+    #[default]
+    Xml,
+    /// Sections in a fixed order by kind and members grouped by kind
+    Kind,
+    /// Sections and members in the order in which they are declared in the source files
+    Declaration,
+    /// Sections by title and members by name
+    Alphabetical,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SortStrategyError {
+    #[error("Unrecognised sort strategy: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for SortStrategy {
+    type Err = SortStrategyError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "xml" => Ok(Self::Xml),
+            "kind" => Ok(Self::Kind),
+            "declaration" => Ok(Self::Declaration),
+            "alphabetical" => Ok(Self::Alphabetical),
+            _ => Err(SortStrategyError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// How to include the members that a class inherits from its base classes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum InheritedMembers {
+    #[default]
+    Hidden,
+    /// Lists of links to the documentation of the members in the base classes
+    Reference,
+    /// Copies of the documentation of the members from the base classes
+    Inline,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InheritedMembersError {
+    #[error("Unrecognised inherited members setting: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for InheritedMembers {
+    type Err = InheritedMembersError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "none" => Ok(Self::Hidden),
+            "reference" => Ok(Self::Reference),
+            "inline" => Ok(Self::Inline),
+            _ => Err(InheritedMembersError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// How to include the documentation comments from within function bodies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum InbodyDescriptions {
+    #[default]
+    Hidden,
+    /// Appended to the detailed description
+    Append,
+    /// Appended to the detailed description under a title
+    Rubric,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InbodyDescriptionsError {
+    #[error("Unrecognised inbody descriptions setting: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for InbodyDescriptions {
+    type Err = InbodyDescriptionsError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "none" => Ok(Self::Hidden),
+            "append" => Ok(Self::Append),
+            "rubric" => Ok(Self::Rubric),
+            _ => Err(InbodyDescriptionsError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// Where to find the source code of functions and classes to include in the output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ImplementationSource {
+    #[default]
+    Hidden,
+    /// The program listing of the file in the doxygen xml, which includes links to other entities
+    Listing,
+    /// The source file under the project root
+    File,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ImplementationSourceError {
+    #[error("Unrecognised implementation source: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for ImplementationSource {
+    type Err = ImplementationSourceError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "none" => Ok(Self::Hidden),
+            "listing" => Ok(Self::Listing),
+            "file" => Ok(Self::File),
+            _ => Err(ImplementationSourceError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// Which location to use for entities which are declared and defined in different places, eg. functions declared
+/// in a header and defined in a source file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SourceLocation {
+    #[default]
+    Definition,
+    Declaration,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SourceLocationError {
+    #[error("Unrecognised source location: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for SourceLocation {
+    type Err = SourceLocationError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "definition" => Ok(Self::Definition),
+            "declaration" => Ok(Self::Declaration),
+            _ => Err(SourceLocationError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// Information from Doxygen's source browser about the members that refer to each other
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceKind {
+    /// The members that a member refers to, eg. the functions that a function calls
+    References,
+    /// The members that refer to a member, eg. the functions that call a function
+    ReferencedBy,
+    /// A graph of the functions that a function calls
+    CallGraph,
+    /// A graph of the functions that call a function
+    CallerGraph,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ReferenceKindError {
+    #[error("Unrecognised reference kind: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for ReferenceKind {
+    type Err = ReferenceKindError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "references" => Ok(Self::References),
+            "referenced-by" => Ok(Self::ReferencedBy),
+            "call-graph" => Ok(Self::CallGraph),
+            "caller-graph" => Ok(Self::CallerGraph),
+            _ => Err(ReferenceKindError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
+/// Graphs from the doxygen xml that can be included in the output
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphKind {
+    /// The base and derived classes of a class
+    Inheritance,
+    /// The classes used by the members of a class
+    Collaboration,
+    /// The files included by a file
+    Include,
+    /// The files that include a file
+    IncludedBy,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GraphKindError {
+    #[error("Unrecognised graph kind: {0}")]
+    Unrecognised(String),
+}
+
+impl std::str::FromStr for GraphKind {
+    type Err = GraphKindError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "inheritance" => Ok(Self::Inheritance),
+            "collaboration" => Ok(Self::Collaboration),
+            "include" => Ok(Self::Include),
+            "included-by" => Ok(Self::IncludedBy),
+            _ => Err(GraphKindError::Unrecognised(str.to_string())),
+        }
+    }
+}
+
 /// Information and options for rendering
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct Context {
     pub project_root: PathBuf,
     pub domain: Option<Domain>,
-    /// A list of things to ignore when rendering
-    pub skip: Vec<Skip>,
+    /// Rules for the things to ignore when rendering
+    pub filter: Filter,
+    /// The members and sections requested for the current directive. Only applies to the top level compound
+    pub selection: Selection,
     pub extension_domain_lookup: HashMap<String, Domain>,
     pub enumerated_list_depth: usize,
+    pub parameter_format: ParameterFormat,
+    /// Rendered types of the parameters of the function that we're currently rendering, keyed by parameter name, so
+    /// that we can show the types in the parameter list when Doxygen doesn't provide them there
+    pub parameter_types: HashMap<String, Vec<Node>>,
+    /// Whether to list the classes that derive from a class in the class' content
+    pub show_derived_classes: bool,
+    /// The graphs to render when they are available in the xml
+    pub graphs: Vec<GraphKind>,
+    pub graph_limits: GraphLimits,
+    pub sort: SortStrategy,
+    /// How to include inherited members. Only applies to the top level compound
+    pub inherited_members: InheritedMembers,
+    /// Whether members without documentation should use the documentation of the member that they reimplement
+    pub inherit_docs: bool,
+    /// Whether to list the members that a member reimplements and the members that reimplement it
+    pub show_reimplements: bool,
+    /// The source browser information to include for each member
+    pub references: Vec<ReferenceKind>,
+    pub inbody_descriptions: InbodyDescriptions,
+    /// The number of characters after which variable initializers and define values are truncated
+    pub initializer_max_length: Option<usize>,
+    pub source_location: SourceLocation,
+    pub implementation_source: ImplementationSource,
+    /// The Pygments lexer for the language of the current compound
+    pub language: Option<&'static str>,
+    /// Whether to show line numbers for program listings
+    pub listing_line_numbers: bool,
+    /// Whether to list the examples which refer to each member
+    pub member_examples: bool,
 }
 
 impl Context {
@@ -62,22 +423,66 @@ impl Context {
             // lose domain information
             .or(self.domain.clone());
 
+        let language = language.and_then(lexer_from_language).or(self.language);
+
         Context {
-            project_root: self.project_root.clone(),
             domain,
-            skip: self.skip.clone(),
-            extension_domain_lookup: self.extension_domain_lookup.clone(),
-            enumerated_list_depth: self.enumerated_list_depth,
+            language,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_selection(&self, selection: Selection) -> Context {
+        Context {
+            selection,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_inherited_members(&self, inherited_members: InheritedMembers) -> Context {
+        Context {
+            inherited_members,
+            ..self.clone()
+        }
+    }
+
+    /// The directive options only apply to the members of the top level compound so inner compounds are rendered
+    /// in full
+    fn for_inner_compound(&self) -> Context {
+        Context {
+            selection: Selection::default(),
+            inherited_members: InheritedMembers::default(),
+            ..self.clone()
         }
     }
 
     fn with_next_enumerated_list_level(&self) -> Context {
         Context {
-            project_root: self.project_root.clone(),
-            domain: self.domain.clone(),
-            skip: self.skip.clone(),
-            extension_domain_lookup: self.extension_domain_lookup.clone(),
             enumerated_list_depth: self.enumerated_list_depth + 1,
+            ..self.clone()
+        }
+    }
+
+    fn with_parameter_types(&self, member_def: &e::MemberdefType) -> Context {
+        // Only the annotated formats show the types
+        if self.parameter_format == ParameterFormat::Names {
+            return self.clone();
+        }
+
+        let parameter_types = member_def
+            .param
+            .iter()
+            .filter_map(|param| match (&param.declname, &param.type_) {
+                (Some(declname), Some(type_)) => {
+                    Some((declname.clone(), render_linked_text_type(self, type_)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Context {
+            parameter_types,
+            ..self.clone()
         }
     }
 
@@ -104,19 +509,26 @@ pub fn render_compounddef_content(
     xml_loader: &mut crate::XmlLoader,
 ) -> anyhow::Result<Vec<Node>> {
     match entry {
-        CompoundDefEntry::SectionDef(section_def) => Ok(vec![render_section_def(
-            ctx,
-            compound_id,
-            compound_kind,
-            section_def,
-        )]),
+        CompoundDefEntry::SectionDef(section_def) => {
+            Ok(
+                render_section_def(ctx, compound_id, compound_kind, section_def, xml_loader)
+                    .into_iter()
+                    .collect(),
+            )
+        }
         CompoundDefEntry::Class(ref_type) => {
+            if !includes_inner_compound(ctx, ref_type) {
+                return Ok(Vec::new());
+            }
+
             let root = xml_loader.load(&ref_type.refid)?;
-            render_compound(ctx, root.as_ref(), inner_groups, xml_loader)
+            let ctx = ctx.for_inner_compound();
+            render_compound(&ctx, root.as_ref(), inner_groups, xml_loader)
         }
         CompoundDefEntry::Group(ref_type) => {
             let root = xml_loader.load(&ref_type.refid)?;
-            render_compound(ctx, root.as_ref(), inner_groups, xml_loader)
+            let ctx = ctx.for_inner_compound();
+            render_compound(&ctx, root.as_ref(), inner_groups, xml_loader)
         }
     }
 }
@@ -131,6 +543,10 @@ pub fn render_compound(
         return Ok(Vec::new());
     };
 
+    if ctx.filter.skip_compound(compound_def) {
+        return Ok(Vec::new());
+    }
+
     let ctx = ctx.with_domain(
         compound_def.location.as_ref(),
         compound_def.language.as_ref(),
@@ -150,18 +566,83 @@ pub fn render_compound(
         content_nodes.append(&mut render_description(&ctx, description));
     }
 
+    if ctx.show_derived_classes && !compound_def.derivedcompoundref.is_empty() {
+        content_nodes.append(&mut render_derived_compound_refs(
+            &compound_def.derivedcompoundref,
+        ));
+    }
+
+    if let Some(ref graph) = compound_def.inheritancegraph {
+        if ctx.graphs.contains(&GraphKind::Inheritance) {
+            content_nodes.append(&mut render_graph_type(
+                &ctx,
+                "Inheritance diagram",
+                &compound_def.id,
+                RankDir::BottomToTop,
+                graph,
+            ));
+        }
+    }
+
+    if let Some(ref graph) = compound_def.collaborationgraph {
+        if ctx.graphs.contains(&GraphKind::Collaboration) {
+            content_nodes.append(&mut render_graph_type(
+                &ctx,
+                "Collaboration diagram",
+                &compound_def.id,
+                RankDir::TopToBottom,
+                graph,
+            ));
+        }
+    }
+
+    if let Some(ref graph) = compound_def.incdepgraph {
+        if ctx.graphs.contains(&GraphKind::Include) {
+            content_nodes.append(&mut render_graph_type(
+                &ctx,
+                "Include dependency graph",
+                &compound_def.id,
+                RankDir::TopToBottom,
+                graph,
+            ));
+        }
+    }
+
+    if let Some(ref graph) = compound_def.invincdepgraph {
+        if ctx.graphs.contains(&GraphKind::IncludedBy) {
+            content_nodes.append(&mut render_graph_type(
+                &ctx,
+                "Included by graph",
+                &compound_def.id,
+                RankDir::BottomToTop,
+                graph,
+            ));
+        }
+    }
+
+    content_nodes.append(&mut render_implementation_source(
+        &ctx,
+        &compound_def.id,
+        compound_def.location.as_ref(),
+        xml_loader,
+    ));
+
     let mut tagged_sections: Vec<_> = compound_def
         .sectiondef
         .iter()
-        .map(|section_def| {
-            (
-                section_def.kind.clone(),
-                render_section_def(&ctx, &compound_def.id, &compound_def.kind, section_def),
+        .filter_map(|section_def| {
+            render_section_def(
+                &ctx,
+                &compound_def.id,
+                &compound_def.kind,
+                section_def,
+                xml_loader,
             )
+            .map(|node| (section_def, node))
         })
         .collect();
 
-    tagged_sections.sort_by(|a, b| cmp_section_kind(&a.0, &b.0));
+    tagged_sections.sort_by(|a, b| cmp_section_defs(&ctx.sort, a.0, b.0));
 
     content_nodes.append(
         &mut tagged_sections
@@ -170,10 +651,27 @@ pub fn render_compound(
             .collect(),
     );
 
+    if ctx.inherited_members != InheritedMembers::Hidden {
+        if let Some(ref list) = compound_def.listofallmembers {
+            content_nodes.append(&mut render_inherited_members(
+                &ctx,
+                compound_def,
+                list,
+                xml_loader,
+            )?);
+        }
+    }
+
+    let inner_ctx = ctx.for_inner_compound();
+
     for innerclass in compound_def.innerclass.iter() {
+        if !includes_inner_compound(&ctx, innerclass) {
+            continue;
+        }
+
         let root = xml_loader.load(&innerclass.refid)?;
         content_nodes.append(&mut render_compound(
-            &ctx,
+            &inner_ctx,
             root.as_ref(),
             inner_groups,
             xml_loader,
@@ -186,7 +684,7 @@ pub fn render_compound(
         for innergroup in compound_def.innergroup.iter() {
             let root = xml_loader.load(&innergroup.refid)?;
             content_nodes.append(&mut render_compound(
-                &ctx,
+                &inner_ctx,
                 root.as_ref(),
                 inner_groups,
                 xml_loader,
@@ -207,10 +705,17 @@ pub fn render_compound(
                 type_: DomainEntryType::Class,
                 target,
                 declaration: text::render_compound_def(domain, compound_def),
-                location: compound_def
-                    .location
-                    .as_ref()
-                    .and_then(|loc| Location::from(&ctx.project_root, loc)),
+                location: render_location(&ctx, compound_def.location.as_ref()),
+                content: content_nodes,
+            }))]);
+        }
+        (Some(Domain::CPlusPlus), e::DoxCompoundKind::Concept) => {
+            return Ok(vec![Node::DomainEntry(Box::new(DomainEntry {
+                domain: Domain::CPlusPlus,
+                type_: DomainEntryType::Concept,
+                target,
+                declaration: text::render_compound_def(&Domain::CPlusPlus, compound_def),
+                location: render_location(&ctx, compound_def.location.as_ref()),
                 content: content_nodes,
             }))]);
         }
@@ -220,10 +725,7 @@ pub fn render_compound(
                 type_: DomainEntryType::Struct,
                 target,
                 declaration: text::render_compound_def(domain, compound_def),
-                location: compound_def
-                    .location
-                    .as_ref()
-                    .and_then(|loc| Location::from(&ctx.project_root, loc)),
+                location: render_location(&ctx, compound_def.location.as_ref()),
                 content: content_nodes,
             }))]);
         }
@@ -233,10 +735,7 @@ pub fn render_compound(
                 type_: DomainEntryType::Union,
                 target,
                 declaration: text::render_compound_def(domain, compound_def),
-                location: compound_def
-                    .location
-                    .as_ref()
-                    .and_then(|loc| Location::from(&ctx.project_root, loc)),
+                location: render_location(&ctx, compound_def.location.as_ref()),
                 content: content_nodes,
             }))]);
         }
@@ -266,7 +765,12 @@ pub fn render_compound(
 }
 
 /// Entry point
-pub fn render_member(ctx: &Context, root: &e::DoxygenType, member_ref_id: &str) -> Vec<Node> {
+pub fn render_member(
+    ctx: &Context,
+    root: &e::DoxygenType,
+    member_ref_id: &str,
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
     let Some(ref compound_def) = root.compounddef else {
         return Vec::new();
     };
@@ -281,103 +785,467 @@ pub fn render_member(ctx: &Context, root: &e::DoxygenType, member_ref_id: &str)
             .memberdef
             .iter()
             .find(|member_def| member_def.id == member_ref_id)
+            .map(|member_def| (section_def, member_def))
     });
 
     match member_def {
-        Some(member_def) => {
-            render_member_def(&ctx, &compound_def.id, &compound_def.kind, member_def)
+        Some((section_def, member_def))
+            if !ctx
+                .filter
+                .skip_member(&compound_def.kind, &section_def.kind, member_def) =>
+        {
+            render_member_def(
+                &ctx,
+                &compound_def.id,
+                &compound_def.kind,
+                member_def,
+                xml_loader,
+            )
         }
-        None => {
+        _ => {
             vec![]
         }
     }
 }
 
-fn render_section_def(
-    ctx: &Context,
-    compound_id: &str,
-    compound_kind: &e::DoxCompoundKind,
-    section_def: &e::SectiondefType,
-) -> Node {
-    let mut content_nodes = vec![Node::Rubric {
-        classes: vec!["docleaf-sectiondef-title".to_string()],
-        nodes: vec![Node::Text(section_title(&section_def.kind))],
-    }];
-
-    content_nodes.append(
-        &mut section_def
-            .memberdef
-            .iter()
-            .flat_map(|element| render_member_def(ctx, compound_id, compound_kind, element))
-            .collect(),
-    );
+/// Inner compounds, like nested classes, are selected by name in the same way as members
+fn includes_inner_compound(ctx: &Context, ref_type: &e::RefType) -> bool {
+    let qualified_name = ref_type.content.as_str();
+    let name = qualified_name.rsplit("::").next().unwrap_or(qualified_name);
 
-    Node::Container(content_nodes)
+    ctx.selection.includes_member(&[name, qualified_name])
 }
 
-const SECTION_ORDER: &[e::DoxSectionKind] = &[
-    e::DoxSectionKind::UserDefined,
-    e::DoxSectionKind::PublicType,
-    e::DoxSectionKind::PublicFunc,
-    e::DoxSectionKind::PublicAttrib,
-    e::DoxSectionKind::PublicSlot,
-    e::DoxSectionKind::Signal,
-    e::DoxSectionKind::DcopFunc,
-    e::DoxSectionKind::Property,
-    e::DoxSectionKind::Event,
-    e::DoxSectionKind::PublicStaticFunc,
-    e::DoxSectionKind::PublicStaticAttrib,
-    e::DoxSectionKind::ProtectedType,
-    e::DoxSectionKind::ProtectedFunc,
-    e::DoxSectionKind::ProtectedAttrib,
-    e::DoxSectionKind::ProtectedSlot,
-    e::DoxSectionKind::ProtectedStaticFunc,
-    e::DoxSectionKind::ProtectedStaticAttrib,
-    e::DoxSectionKind::PackageType,
-    e::DoxSectionKind::PackageFunc,
-    e::DoxSectionKind::PackageAttrib,
-    e::DoxSectionKind::PackageStaticFunc,
-    e::DoxSectionKind::PackageStaticAttrib,
-    e::DoxSectionKind::PrivateType,
-    e::DoxSectionKind::PrivateFunc,
-    e::DoxSectionKind::PrivateAttrib,
-    e::DoxSectionKind::PrivateSlot,
-    e::DoxSectionKind::PrivateStaticFunc,
-    e::DoxSectionKind::PrivateStaticAttrib,
-    e::DoxSectionKind::Friend,
-    e::DoxSectionKind::Related,
-    e::DoxSectionKind::Define,
-    e::DoxSectionKind::Prototype,
-    e::DoxSectionKind::Typedef,
-    e::DoxSectionKind::Enum,
-    e::DoxSectionKind::Func,
-    e::DoxSectionKind::Var,
-];
-
-fn cmp_section_kind(a: &e::DoxSectionKind, b: &e::DoxSectionKind) -> Ordering {
-    let a_index = SECTION_ORDER
+fn render_derived_compound_refs(compound_refs: &[e::CompoundRefType]) -> Vec<Node> {
+    let items = compound_refs
         .iter()
-        .position(|entry| a == entry)
-        .unwrap_or(0);
+        .map(|compound_ref| {
+            let name = Node::Text(compound_ref.content.clone());
+
+            // Derived classes that Doxygen hasn't documented have no refid so we can't link to them and those that it
+            // has documented might not be included in the Sphinx documentation
+            let node = match compound_ref.refid {
+                Some(ref refid) => Node::OptionalReference {
+                    refid: refid.clone(),
+                    children: vec![name],
+                },
+                None => name,
+            };
 
-    let b_index = SECTION_ORDER
-        .iter()
-        .position(|entry| b == entry)
-        .unwrap_or(0);
+            Node::ListItem(vec![Node::Paragraph(vec![node])])
+        })
+        .collect();
 
-    a_index.cmp(&b_index)
+    vec![
+        Node::Rubric {
+            classes: vec!["docleaf-derived-classes-title".to_string()],
+            nodes: vec![Node::Text("Derived classes".to_string())],
+        },
+        Node::BulletList(items),
+    ]
 }
 
-fn section_title(section_kind: &e::DoxSectionKind) -> String {
-    match section_kind {
-        e::DoxSectionKind::UserDefined => "User Defined".to_string(),
-        e::DoxSectionKind::PublicType => "Public Types".to_string(),
-        e::DoxSectionKind::PublicFunc => "Public Functions".to_string(),
-        e::DoxSectionKind::PublicAttrib => "Public Attributes".to_string(),
-        e::DoxSectionKind::PublicSlot => "Public Slots".to_string(),
-        e::DoxSectionKind::Signal => "Signals".to_string(),
-        e::DoxSectionKind::DcopFunc => "DCOP Functions".to_string(),
-        e::DoxSectionKind::Property => "Properties".to_string(),
+/// Renders the members from the 'listofallmembers' which are declared in base classes, grouped by the base class
+/// that declares them
+fn render_inherited_members(
+    ctx: &Context,
+    compound_def: &e::CompounddefType,
+    list: &e::ListofallmembersType,
+    xml_loader: &mut XmlLoader,
+) -> anyhow::Result<Vec<Node>> {
+    let mut groups: Vec<(&str, Vec<&e::MemberRefType>)> = Vec::new();
+
+    for member in list.member.iter() {
+        // Private members of the base classes aren't accessible from the derived class
+        if member.scope == compound_def.compoundname || member.prot == e::DoxProtectionKind::Private
+        {
+            continue;
+        }
+
+        let qualified_name = format!("{}::{}", member.scope, member.name);
+        if !ctx
+            .selection
+            .includes_member(&[&member.name, &qualified_name])
+        {
+            continue;
+        }
+
+        match groups.iter_mut().find(|(scope, _)| *scope == member.scope) {
+            Some((_, members)) => members.push(member),
+            None => groups.push((&member.scope, vec![member])),
+        }
+    }
+
+    let mut nodes = Vec::new();
+
+    for (scope, members) in groups {
+        let base_refid = compound_refid(&members[0].refid);
+
+        let items = match ctx.inherited_members {
+            InheritedMembers::Hidden => continue,
+            InheritedMembers::Reference => vec![Node::BulletList(
+                members
+                    .iter()
+                    .map(|member| render_member_ref(member))
+                    .collect(),
+            )],
+            InheritedMembers::Inline => {
+                let root = xml_loader.load(base_refid)?;
+                render_inherited_member_defs(
+                    ctx,
+                    &compound_def.id,
+                    root.as_ref(),
+                    &members,
+                    xml_loader,
+                )
+            }
+        };
+
+        if items.is_empty() {
+            continue;
+        }
+
+        nodes.push(Node::Rubric {
+            classes: vec!["docleaf-inherited-members-title".to_string()],
+            nodes: vec![
+                Node::Text("Inherited from ".to_string()),
+                // Base classes, eg. from third party libraries, might not be in the Sphinx documentation
+                Node::OptionalReference {
+                    refid: base_refid.to_string(),
+                    children: vec![Node::Text(scope.to_string())],
+                },
+            ],
+        });
+        nodes.extend(items);
+    }
+
+    Ok(nodes)
+}
+
+/// Doxygen member ids are made up of the id of the compound that declares the member followed by '_1' and a hash
+fn compound_refid(member_refid: &str) -> &str {
+    member_refid
+        .rsplit_once("_1")
+        .map_or(member_refid, |(compound_refid, _)| compound_refid)
+}
+
+fn render_member_ref(member: &e::MemberRefType) -> Node {
+    // The ambiguity scope distinguishes between members with the same name inherited through different base classes
+    let name = format!(
+        "{}{}",
+        member.ambiguityscope.as_deref().unwrap_or_default(),
+        member.name
+    );
+
+    let mut qualifiers = Vec::new();
+
+    match member.prot {
+        e::DoxProtectionKind::Protected => qualifiers.push("protected"),
+        e::DoxProtectionKind::Package => qualifiers.push("package"),
+        e::DoxProtectionKind::Public | e::DoxProtectionKind::Private => {}
+    }
+
+    match member.virt {
+        e::DoxVirtualKind::Virtual => qualifiers.push("virtual"),
+        e::DoxVirtualKind::PureVirtual => qualifiers.push("pure virtual"),
+        e::DoxVirtualKind::NonVirtual => {}
+    }
+
+    let mut nodes = vec![Node::OptionalReference {
+        refid: member.refid.clone(),
+        children: vec![Node::Literal(vec![Node::Text(name)])],
+    }];
+
+    if !qualifiers.is_empty() {
+        nodes.push(Node::Text(format!(" ({})", qualifiers.join(", "))));
+    }
+
+    Node::ListItem(vec![Node::Paragraph(nodes)])
+}
+
+/// Renders copies of the members from the base compound. The copies are given their own targets so that they don't
+/// clash with the documentation of the members in the base compound
+fn render_inherited_member_defs(
+    ctx: &Context,
+    compound_id: &str,
+    root: &e::DoxygenType,
+    members: &[&e::MemberRefType],
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
+    let Some(ref base_def) = root.compounddef else {
+        return Vec::new();
+    };
+
+    let base_ctx = ctx.with_domain(base_def.location.as_ref(), base_def.language.as_ref());
+
+    let mut nodes = Vec::new();
+
+    for member in members {
+        let member_def = base_def.sectiondef.iter().find_map(|section_def| {
+            section_def
+                .memberdef
+                .iter()
+                .find(|member_def| member_def.id == member.refid)
+                .map(|member_def| (section_def, member_def))
+        });
+
+        let Some((section_def, member_def)) = member_def else {
+            continue;
+        };
+
+        if ctx
+            .filter
+            .skip_member(&base_def.kind, &section_def.kind, member_def)
+        {
+            continue;
+        }
+
+        let id = format!("{compound_id}-{}", member_def.id);
+        for mut node in render_member_def(
+            &base_ctx,
+            &base_def.id,
+            &base_def.kind,
+            member_def,
+            xml_loader,
+        ) {
+            set_target_id(&mut node, &id);
+            nodes.push(node);
+        }
+    }
+
+    nodes
+}
+
+fn set_target_id(node: &mut Node, id: &str) {
+    match node {
+        Node::Target(target) => {
+            target.ids = id.to_string();
+            target.names = id.to_string();
+        }
+        Node::DomainEntry(entry) => {
+            entry.target.ids = id.to_string();
+            entry.target.names = id.to_string();
+        }
+        Node::Desc {
+            signature_lines, ..
+        } => signature_lines
+            .iter_mut()
+            .for_each(|node| set_target_id(node, id)),
+        Node::DescSignature(_, nodes) | Node::DescSignatureLine(nodes) => {
+            nodes.iter_mut().for_each(|node| set_target_id(node, id))
+        }
+        _ => {}
+    }
+}
+
+fn render_graph_type(
+    ctx: &Context,
+    title: &str,
+    root_refid: &str,
+    rank_dir: RankDir,
+    graph: &e::GraphType,
+) -> Vec<Node> {
+    let graph = dot::render_graph(graph, root_refid, rank_dir, &ctx.graph_limits);
+
+    vec![
+        Node::Rubric {
+            classes: vec!["docleaf-graph-title".to_string()],
+            nodes: vec![Node::Text(title.to_string())],
+        },
+        Node::Graphviz {
+            code: graph.code,
+            alt: title.to_string(),
+            refids: graph.refids,
+        },
+    ]
+}
+
+fn render_section_def(
+    ctx: &Context,
+    compound_id: &str,
+    compound_kind: &e::DoxCompoundKind,
+    section_def: &e::SectiondefType,
+    xml_loader: &mut XmlLoader,
+) -> Option<Node> {
+    if ctx.filter.skip_section(compound_kind, section_def)
+        || !ctx.selection.includes_section(section_def)
+    {
+        return None;
+    }
+
+    let mut member_defs: Vec<_> = section_def
+        .memberdef
+        .iter()
+        .filter(|member_def| {
+            !ctx.filter
+                .skip_member(compound_kind, &section_def.kind, member_def)
+        })
+        .filter(|member_def| {
+            let qualified_name = member_def.qualifiedname.as_deref().unwrap_or_default();
+            ctx.selection
+                .includes_member(&[&member_def.name, qualified_name])
+        })
+        .collect();
+
+    // Avoid rendering a section title with nothing under it
+    if member_defs.is_empty() {
+        return None;
+    }
+
+    member_defs.sort_by(|a, b| cmp_member_defs(&ctx.sort, a, b));
+
+    let mut content_nodes = vec![Node::Rubric {
+        classes: vec!["docleaf-sectiondef-title".to_string()],
+        nodes: vec![Node::Text(section_title(&section_def.kind))],
+    }];
+
+    content_nodes.append(
+        &mut member_defs
+            .into_iter()
+            .flat_map(|element| {
+                render_member_def(ctx, compound_id, compound_kind, element, xml_loader)
+            })
+            .collect(),
+    );
+
+    Some(Node::Container(content_nodes))
+}
+
+const SECTION_ORDER: &[e::DoxSectionKind] = &[
+    e::DoxSectionKind::UserDefined,
+    e::DoxSectionKind::PublicType,
+    e::DoxSectionKind::PublicFunc,
+    e::DoxSectionKind::PublicAttrib,
+    e::DoxSectionKind::PublicSlot,
+    e::DoxSectionKind::Signal,
+    e::DoxSectionKind::DcopFunc,
+    e::DoxSectionKind::Property,
+    e::DoxSectionKind::Event,
+    e::DoxSectionKind::PublicStaticFunc,
+    e::DoxSectionKind::PublicStaticAttrib,
+    e::DoxSectionKind::ProtectedType,
+    e::DoxSectionKind::ProtectedFunc,
+    e::DoxSectionKind::ProtectedAttrib,
+    e::DoxSectionKind::ProtectedSlot,
+    e::DoxSectionKind::ProtectedStaticFunc,
+    e::DoxSectionKind::ProtectedStaticAttrib,
+    e::DoxSectionKind::PackageType,
+    e::DoxSectionKind::PackageFunc,
+    e::DoxSectionKind::PackageAttrib,
+    e::DoxSectionKind::PackageStaticFunc,
+    e::DoxSectionKind::PackageStaticAttrib,
+    e::DoxSectionKind::PrivateType,
+    e::DoxSectionKind::PrivateFunc,
+    e::DoxSectionKind::PrivateAttrib,
+    e::DoxSectionKind::PrivateSlot,
+    e::DoxSectionKind::PrivateStaticFunc,
+    e::DoxSectionKind::PrivateStaticAttrib,
+    e::DoxSectionKind::Friend,
+    e::DoxSectionKind::Related,
+    e::DoxSectionKind::Define,
+    e::DoxSectionKind::Prototype,
+    e::DoxSectionKind::Typedef,
+    e::DoxSectionKind::Enum,
+    e::DoxSectionKind::Func,
+    e::DoxSectionKind::Var,
+];
+
+fn cmp_section_kind(a: &e::DoxSectionKind, b: &e::DoxSectionKind) -> Ordering {
+    let a_index = SECTION_ORDER
+        .iter()
+        .position(|entry| a == entry)
+        .unwrap_or(0);
+
+    let b_index = SECTION_ORDER
+        .iter()
+        .position(|entry| b == entry)
+        .unwrap_or(0);
+
+    a_index.cmp(&b_index)
+}
+
+const MEMBER_KIND_ORDER: &[e::DoxMemberKind] = &[
+    e::DoxMemberKind::Define,
+    e::DoxMemberKind::Typedef,
+    e::DoxMemberKind::Enum,
+    e::DoxMemberKind::Function,
+    e::DoxMemberKind::Variable,
+];
+
+fn member_kind_index(member_kind: &e::DoxMemberKind) -> usize {
+    MEMBER_KIND_ORDER
+        .iter()
+        .position(|entry| member_kind == entry)
+        .unwrap_or(MEMBER_KIND_ORDER.len())
+}
+
+/// Sort stably so that entries that compare equal keep their order from the xml
+fn cmp_section_defs(sort: &SortStrategy, a: &e::SectiondefType, b: &e::SectiondefType) -> Ordering {
+    let a_location = section_location(a);
+    let b_location = section_location(b);
+
+    match sort {
+        SortStrategy::Xml => cmp_section_kind(&a.kind, &b.kind),
+        SortStrategy::Kind => {
+            cmp_section_kind(&a.kind, &b.kind).then_with(|| cmp_declaration(a_location, b_location))
+        }
+        SortStrategy::Declaration => cmp_declaration(a_location, b_location),
+        SortStrategy::Alphabetical => section_title(&a.kind)
+            .cmp(&section_title(&b.kind))
+            .then_with(|| cmp_declaration(a_location, b_location)),
+    }
+}
+
+fn cmp_member_defs(sort: &SortStrategy, a: &e::MemberdefType, b: &e::MemberdefType) -> Ordering {
+    let a_location = a.location.as_ref();
+    let b_location = b.location.as_ref();
+
+    match sort {
+        SortStrategy::Xml => Ordering::Equal,
+        SortStrategy::Kind => member_kind_index(&a.kind)
+            .cmp(&member_kind_index(&b.kind))
+            .then_with(|| cmp_declaration(a_location, b_location)),
+        SortStrategy::Declaration => cmp_declaration(a_location, b_location),
+        SortStrategy::Alphabetical => a
+            .name
+            .cmp(&b.name)
+            .then_with(|| cmp_declaration(a_location, b_location)),
+    }
+}
+
+/// The location of the first declared member in the section
+fn section_location(section_def: &e::SectiondefType) -> Option<&e::LocationType> {
+    section_def
+        .memberdef
+        .iter()
+        .filter_map(|member_def| member_def.location.as_ref())
+        .min_by(|a, b| cmp_declaration(Some(a), Some(b)))
+}
+
+/// Orders by file and then line with entries without locations, or lines, at the end. Line numbers are only
+/// compared within the same file
+fn cmp_declaration(a: Option<&e::LocationType>, b: Option<&e::LocationType>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a
+            .file
+            .cmp(&b.file)
+            .then_with(|| a.line.unwrap_or(i32::MAX).cmp(&b.line.unwrap_or(i32::MAX))),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn section_title(section_kind: &e::DoxSectionKind) -> String {
+    match section_kind {
+        e::DoxSectionKind::UserDefined => "User Defined".to_string(),
+        e::DoxSectionKind::PublicType => "Public Types".to_string(),
+        e::DoxSectionKind::PublicFunc => "Public Functions".to_string(),
+        e::DoxSectionKind::PublicAttrib => "Public Attributes".to_string(),
+        e::DoxSectionKind::PublicSlot => "Public Slots".to_string(),
+        e::DoxSectionKind::Signal => "Signals".to_string(),
+        e::DoxSectionKind::DcopFunc => "DCOP Functions".to_string(),
+        e::DoxSectionKind::Property => "Properties".to_string(),
         e::DoxSectionKind::Event => "Events".to_string(),
         e::DoxSectionKind::PublicStaticFunc => "Public Static Functions".to_string(),
         e::DoxSectionKind::PublicStaticAttrib => "Public Static Attributes".to_string(),
@@ -414,238 +1282,1125 @@ pub fn render_member_def(
     compound_id: &str,
     compound_kind: &e::DoxCompoundKind,
     member_def: &e::MemberdefType,
+    xml_loader: &mut XmlLoader,
 ) -> Vec<Node> {
-    // Ignore variables and functions that have all-caps names if the skip option is provided as they are often
-    // mishandled macros and shouldn't really be included in the output as they will cause issues with the Sphinx
-    // domains
-    //
-    // TODO: We could explore rendering them but not sending them through the domain handling process as that is
-    // the root of our issue
-    if matches!(
-        member_def.kind,
-        e::DoxMemberKind::Variable | e::DoxMemberKind::Function
-    ) && ctx.skip.contains(&Skip::MembersAllCaps)
-        && is_upper_snake_case(&member_def.name)
-    {
-        return Vec::new();
+    let name = member_kind_name(&member_def.kind);
+    let mut content_nodes = Vec::new();
+
+    // Create a new context with the location information if it is there and the parameter types so that we can
+    // include them in the parameter list in the description
+    let ctx = &ctx
+        .with_domain(member_def.location.as_ref(), None)
+        .with_parameter_types(member_def);
+
+    if let Some(ref description) = member_def.briefdescription {
+        content_nodes.append(&mut render_description(ctx, description));
+    }
+
+    if let Some(ref description) = member_def.detaileddescription {
+        content_nodes.append(&mut render_description(ctx, description));
+    }
+
+    if ctx.inherit_docs && content_nodes.is_empty() {
+        content_nodes.append(&mut render_inherited_description(
+            ctx, member_def, xml_loader,
+        ));
+    }
+
+    if let Some(ref description) = member_def.inbodydescription {
+        content_nodes.append(&mut render_inbody_description(ctx, description));
+    }
+
+    if ctx.show_reimplements && !member_def.reimplements.is_empty() {
+        content_nodes.append(&mut render_reimplement_refs(
+            "Reimplements",
+            &member_def.reimplements,
+            xml_loader,
+        ));
+    }
+
+    if ctx.show_reimplements && !member_def.reimplementedby.is_empty() {
+        content_nodes.append(&mut render_reimplement_refs(
+            "Reimplemented by",
+            &member_def.reimplementedby,
+            xml_loader,
+        ));
+    }
+
+    if !ctx.references.is_empty() {
+        content_nodes.append(&mut render_member_references(ctx, member_def));
     }
 
-    let name = member_kind_name(&member_def.kind);
-    let mut content_nodes = Vec::new();
+    content_nodes.append(&mut render_implementation_source(
+        ctx,
+        &member_def.id,
+        member_def.location.as_ref(),
+        xml_loader,
+    ));
+
+    if ctx.member_examples {
+        content_nodes.append(&mut render_member_examples(&member_def.id, xml_loader));
+    }
+
+    let ids = member_def.id.clone();
+    let names = member_def.id.clone();
+    let target = Target { ids, names };
+
+    let signature_line;
+
+    let basic_signature_line = |target| {
+        vec![
+            Node::Target(target),
+            Node::DescSignatureKeyword(vec![Node::Text(name)]),
+            Node::DescSignatureSpace,
+            Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
+        ]
+    };
+
+    match member_def.kind {
+        e::DoxMemberKind::Enum => {
+            // Pass through the name or the id if there is no name. The '@' is used by Sphinx to
+            // determine anonymous enums
+            let enum_name = if member_def.name.is_empty() {
+                format!("@{}", member_def.id)
+            } else {
+                member_def.name.clone()
+            };
+
+            content_nodes.append(
+                &mut member_def
+                    .enumvalue
+                    .iter()
+                    .map(|element| {
+                        render_enum_value(ctx, &enum_name, member_def.location.as_ref(), element)
+                    })
+                    .collect(),
+            );
+
+            // Early exit if there is domain information for rendering this entry
+            if let Some(ref domain) = ctx.domain {
+                return vec![Node::DomainEntry(Box::new(DomainEntry {
+                    domain: domain.clone(),
+                    type_: DomainEntryType::Enum,
+                    target,
+                    declaration: text::render_member_def(domain, member_def),
+                    location: render_location(ctx, member_def.location.as_ref()),
+                    content: content_nodes,
+                }))];
+            }
+
+            signature_line = basic_signature_line(target);
+        }
+        e::DoxMemberKind::Function => {
+            // Early exit if there is domain information for rendering this entry
+            if let Some(ref domain) = ctx.domain {
+                return vec![Node::DomainEntry(Box::new(DomainEntry {
+                    domain: domain.clone(),
+                    type_: DomainEntryType::Function,
+                    target,
+                    declaration: text::render_member_def(domain, member_def),
+                    location: render_location(ctx, member_def.location.as_ref()),
+                    content: content_nodes,
+                }))];
+            }
+
+            let parameter_list_items = member_def
+                .param
+                .iter()
+                .map(|param| {
+                    let mut param_contents = Vec::new();
+
+                    match (&param.type_, &param.declname) {
+                        (Some(ref type_), Some(ref declname)) => {
+                            param_contents.append(&mut render_linked_text_type(ctx, type_));
+                            param_contents.push(Node::DescSignatureSpace);
+                            param_contents.push(Node::DescSignatureName(declname.clone()));
+                        }
+                        (Some(ref type_), None) => {
+                            param_contents.append(&mut render_linked_text_type(ctx, type_));
+                        }
+                        (None, Some(ref declname)) => {
+                            param_contents.push(Node::DescSignatureName(declname.clone()));
+                        }
+                        (None, None) => {}
+                    };
+
+                    Node::DescParameter(param_contents)
+                })
+                .collect();
+
+            match member_def.type_ {
+                Some(ref type_) => {
+                    signature_line = vec![
+                        Node::Target(target),
+                        Node::DescSignatureKeyword(render_linked_text_type(ctx, type_)),
+                        Node::DescSignatureSpace,
+                        Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
+                        Node::DescParameterList(parameter_list_items),
+                    ];
+                }
+                None => {
+                    signature_line = vec![
+                        Node::Target(target),
+                        Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
+                        Node::DescParameterList(parameter_list_items),
+                    ];
+                }
+            }
+        }
+        e::DoxMemberKind::Define => {
+            let value = member_def
+                .initializer
+                .as_ref()
+                .map(|initializer| {
+                    text::render_initializer(initializer, ctx.initializer_max_length)
+                })
+                .filter(|value| !value.is_empty());
+
+            // Early exit if there is domain information for rendering this entry
+            if let Some(ref domain) = ctx.domain {
+                // The Sphinx macro directives don't accept a value in the signature so we include it in the content
+                if let Some(value) = value {
+                    content_nodes.insert(
+                        0,
+                        Node::Paragraph(vec![
+                            Node::Text("Value: ".to_string()),
+                            Node::Literal(vec![Node::Text(value)]),
+                        ]),
+                    );
+                }
+
+                return vec![Node::DomainEntry(Box::new(DomainEntry {
+                    domain: domain.clone(),
+                    type_: DomainEntryType::Define,
+                    target,
+                    declaration: text::render_member_def(domain, member_def),
+                    location: render_location(ctx, member_def.location.as_ref()),
+                    content: content_nodes,
+                }))];
+            }
+
+            signature_line = basic_signature_line(target)
+                .into_iter()
+                .chain(
+                    value
+                        .into_iter()
+                        .flat_map(|value| [Node::DescSignatureSpace, Node::Text(value)]),
+                )
+                .collect();
+        }
+        e::DoxMemberKind::Variable => {
+            // Don't return any nodes for an anonymous union member as we don't have a good representation for it
+            // and it ends up confusing Sphinx domains a bit
+            if variable_member_def_is_anonymous_union(member_def) {
+                return vec![];
+            }
+
+            // Don't return any nodes if the variable is actually a struct inside a union and the struct's id matches
+            // the compound id that we're inside as this represents a confused, perhaps failure, case in Doxygen where
+            // this are not linked up properly and rendering the resulting entry wouldn't make sense
+            if compound_kind == &e::DoxCompoundKind::Union
+                && variable_member_def_is_struct_matching_id(member_def, compound_id)
+            {
+                return vec![];
+            }
+
+            // Early exit if there is domain information for rendering this entry
+            if let Some(ref domain) = ctx.domain {
+                return vec![Node::DomainEntry(Box::new(DomainEntry {
+                    domain: domain.clone(),
+                    // All variables in the memberdef context are just members of the compound
+                    // TODO: This might not be the case for variables in 'file' compounds
+                    type_: DomainEntryType::Member,
+                    target,
+                    declaration: text::render_member_def(domain, member_def),
+                    location: render_location(ctx, member_def.location.as_ref()),
+                    content: content_nodes,
+                }))];
+            }
+
+            let bitfield = member_def.bitfield.as_deref().map(text::render_bitfield);
+            let initializer = member_def.initializer.as_ref().map(|initializer| {
+                text::render_initializer(initializer, ctx.initializer_max_length)
+            });
+
+            signature_line = basic_signature_line(target)
+                .into_iter()
+                .chain(
+                    [bitfield, initializer]
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|text| [Node::DescSignatureSpace, Node::Text(text)]),
+                )
+                .collect();
+        }
+        e::DoxMemberKind::Typedef => {
+            // Early exit if there is domain information for rendering this entry
+            if let Some(ref domain) = ctx.domain {
+                return vec![Node::DomainEntry(Box::new(DomainEntry {
+                    domain: domain.clone(),
+                    type_: DomainEntryType::Typedef,
+                    target,
+                    declaration: text::render_member_def(domain, member_def),
+                    location: render_location(ctx, member_def.location.as_ref()),
+                    content: content_nodes,
+                }))];
+            }
+
+            signature_line = basic_signature_line(target);
+        }
+        _ => {
+            signature_line = basic_signature_line(target);
+        }
+    };
+
+    let content = Node::DescContent(content_nodes);
+
+    vec![Node::Desc {
+        object_type: text::render_member_kind(&member_def.kind).to_string(),
+        domain: ctx.domain.clone(),
+        signature_lines: vec![Node::DescSignature(
+            SignatureType::MultiLine,
+            vec![Node::DescSignatureLine(signature_line)],
+        )],
+        content: Box::new(content),
+    }]
+}
+
+fn render_inbody_description(ctx: &Context, description: &e::DescriptionType) -> Vec<Node> {
+    if ctx.inbody_descriptions == InbodyDescriptions::Hidden {
+        return Vec::new();
+    }
+
+    let mut nodes = render_description(ctx, description);
+
+    if ctx.inbody_descriptions == InbodyDescriptions::Rubric && !nodes.is_empty() {
+        nodes.insert(
+            0,
+            Node::Rubric {
+                classes: vec!["docleaf-inbody-description-title".to_string()],
+                nodes: vec![Node::Text("Implementation notes".to_string())],
+            },
+        );
+    }
+
+    nodes
+}
+
+/// Renders a Doxygen page, including the main page, as a section containing the page's own sections followed by
+/// its sub-pages
+pub fn render_page(
+    ctx: &Context,
+    root: &e::DoxygenType,
+    content_only: bool,
+    xml_loader: &mut XmlLoader,
+) -> anyhow::Result<Vec<Node>> {
+    let Some(ref compound_def) = root.compounddef else {
+        return Ok(Vec::new());
+    };
+
+    if ctx.filter.skip_compound(compound_def) {
+        return Ok(Vec::new());
+    }
+
+    let mut content_nodes = Vec::new();
+
+    if let Some(ref table_of_contents) = compound_def.tableofcontents {
+        content_nodes.push(render_table_of_contents_type(table_of_contents));
+    }
+
+    if let Some(ref description) = compound_def.briefdescription {
+        content_nodes.append(&mut render_description(ctx, description));
+    }
+
+    if let Some(ref description) = compound_def.detaileddescription {
+        content_nodes.append(&mut render_description(ctx, description));
+
+        for sect in description.sect1.iter() {
+            content_nodes.push(render_doc_sect1_type(ctx, sect));
+        }
+    }
+
+    for inner_page in compound_def.innerpage.iter() {
+        let root = xml_loader.load(&inner_page.refid)?;
+        content_nodes.append(&mut render_page(ctx, &root, false, xml_loader)?);
+    }
+
+    if content_only {
+        return Ok(content_nodes);
+    }
+
+    let title = compound_def
+        .title
+        .clone()
+        .unwrap_or_else(|| compound_def.compoundname.clone());
+
+    Ok(vec![Node::Section {
+        target: Target {
+            ids: compound_def.id.clone(),
+            names: compound_def.id.clone(),
+        },
+        title: vec![Node::Text(title)],
+        nodes: content_nodes,
+    }])
+}
+
+/// Renders a Doxygen directory with its description followed by a list of the files and directories within it.
+/// Sub-directories have their contents listed until the depth, counting the directory's own contents as the first
+/// level, is reached
+pub fn render_dir(
+    ctx: &Context,
+    root: &e::DoxygenType,
+    depth: Option<usize>,
+    xml_loader: &mut XmlLoader,
+) -> anyhow::Result<Vec<Node>> {
+    let Some(ref compound_def) = root.compounddef else {
+        return Ok(Vec::new());
+    };
+
+    if ctx.filter.skip_compound(compound_def) {
+        return Ok(Vec::new());
+    }
+
+    let mut content_nodes = Vec::new();
+
+    if let Some(ref description) = compound_def.briefdescription {
+        content_nodes.append(&mut render_description(ctx, description));
+    }
+
+    if let Some(ref description) = compound_def.detaileddescription {
+        content_nodes.append(&mut render_description(ctx, description));
+    }
+
+    if depth != Some(0) {
+        let items = render_dir_contents(ctx, compound_def, depth, xml_loader);
+        if !items.is_empty() {
+            content_nodes.push(Node::BulletList(items));
+        }
+    }
+
+    let target = Target {
+        ids: compound_def.id.clone(),
+        names: compound_def.id.clone(),
+    };
+
+    let kind = text::render_compound_kind(&compound_def.kind);
+
+    Ok(vec![Node::Desc {
+        object_type: kind.to_string(),
+        domain: ctx.domain.clone(),
+        signature_lines: vec![Node::DescSignature(
+            SignatureType::MultiLine,
+            vec![Node::DescSignatureLine(vec![
+                Node::Target(target),
+                Node::DescSignatureKeyword(vec![Node::Text(kind.to_string())]),
+                Node::DescSignatureSpace,
+                Node::DescName(Box::new(Node::DescSignatureName(
+                    compound_def.compoundname.clone(),
+                ))),
+            ])],
+        )],
+        content: Box::new(Node::DescContent(content_nodes)),
+    }])
+}
+
+/// Renders a list item for each sub-directory and file in the directory with a link and the brief description. Entries
+/// which can't be loaded are left out rather than failing the whole directory
+fn render_dir_contents(
+    ctx: &Context,
+    compound_def: &e::CompounddefType,
+    depth: Option<usize>,
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
+    let mut items = Vec::new();
+
+    for inner_dir in compound_def.innerdir.iter() {
+        let root = match xml_loader.load(&inner_dir.refid) {
+            Ok(root) => root,
+            Err(err) => {
+                tracing::warn!("Unable to load directory {}: {err}", inner_dir.refid);
+                continue;
+            }
+        };
+        let Some(ref dir_def) = root.compounddef else {
+            continue;
+        };
+
+        if ctx.filter.skip_compound(dir_def) {
+            continue;
+        }
+
+        // Doxygen gives the full path of inner directories so we only show the last part
+        let name = inner_dir.content.trim_end_matches('/');
+        let name = name.rsplit('/').next().unwrap_or(name);
+
+        let mut nodes = render_dir_entry(ctx, &inner_dir.refid, &format!("{name}/"), dir_def);
+
+        let depth = depth.map(|depth| depth.saturating_sub(1));
+        if depth != Some(0) {
+            let inner_items = render_dir_contents(ctx, dir_def, depth, xml_loader);
+            if !inner_items.is_empty() {
+                nodes.push(Node::BulletList(inner_items));
+            }
+        }
+
+        items.push(Node::ListItem(nodes));
+    }
+
+    for inner_file in compound_def.innerfile.iter() {
+        let root = match xml_loader.load(&inner_file.refid) {
+            Ok(root) => root,
+            Err(err) => {
+                tracing::warn!("Unable to load file {}: {err}", inner_file.refid);
+                continue;
+            }
+        };
+        let Some(ref file_def) = root.compounddef else {
+            continue;
+        };
+
+        if ctx.filter.skip_compound(file_def) {
+            continue;
+        }
+
+        items.push(Node::ListItem(render_dir_entry(
+            ctx,
+            &inner_file.refid,
+            &inner_file.content,
+            file_def,
+        )));
+    }
+
+    items
+}
+
+fn render_dir_entry(
+    ctx: &Context,
+    refid: &str,
+    name: &str,
+    compound_def: &e::CompounddefType,
+) -> Vec<Node> {
+    // The file or directory might not be rendered by any directive
+    let mut nodes = vec![Node::Paragraph(vec![Node::OptionalReference {
+        refid: refid.to_string(),
+        children: vec![Node::Literal(vec![Node::Text(name.to_string())])],
+    }])];
+
+    if let Some(ref description) = compound_def.briefdescription {
+        nodes.append(&mut render_description(ctx, description));
+    }
+
+    nodes
+}
+
+/// Renders a Doxygen example as a section containing its description and the full program listing
+pub fn render_example(
+    ctx: &Context,
+    root: &e::DoxygenType,
+    content_only: bool,
+) -> anyhow::Result<Vec<Node>> {
+    let Some(ref compound_def) = root.compounddef else {
+        return Ok(Vec::new());
+    };
+
+    if ctx.filter.skip_compound(compound_def) {
+        return Ok(Vec::new());
+    }
+
+    let ctx = ctx.with_domain(
+        compound_def.location.as_ref(),
+        compound_def.language.as_ref(),
+    );
+
+    let mut content_nodes = Vec::new();
+
+    if let Some(ref description) = compound_def.briefdescription {
+        content_nodes.append(&mut render_description(&ctx, description));
+    }
+
+    // Doxygen includes the listing of the example at the end of the detailed description so we only expect a separate
+    // program listing for the example in unusual cases
+    if let Some(ref description) = compound_def.detaileddescription {
+        content_nodes.append(&mut render_description(&ctx, description));
+    }
+
+    if let Some(ref listing) = compound_def.programlisting {
+        content_nodes.push(render_listing_type(&ctx, listing));
+    }
+
+    if content_only {
+        return Ok(content_nodes);
+    }
+
+    let title = compound_def
+        .title
+        .clone()
+        .unwrap_or_else(|| compound_def.compoundname.clone());
+
+    Ok(vec![Node::Section {
+        target: Target {
+            ids: compound_def.id.clone(),
+            names: compound_def.id.clone(),
+        },
+        title: vec![Node::Text(title)],
+        nodes: content_nodes,
+    }])
+}
+
+/// Renders links to the examples whose listings refer to the member. Doxygen doesn't record this relationship on the
+/// member in the XML so we search the listings of every example
+fn render_member_examples(refid: &str, xml_loader: &mut XmlLoader) -> Vec<Node> {
+    let member_examples = xml_loader.member_examples();
+    let Some(examples) = member_examples.get(refid) else {
+        return Vec::new();
+    };
+
+    let items = examples
+        .iter()
+        .map(|example| {
+            // Examples are only linked when they are rendered by a 'doxygenexample' directive
+            Node::ListItem(vec![Node::Paragraph(vec![Node::OptionalReference {
+                refid: example.refid.clone(),
+                children: vec![Node::Text(example.name.clone())],
+            }])])
+        })
+        .collect();
+
+    vec![
+        Node::Rubric {
+            classes: vec!["docleaf-examples-title".to_string()],
+            nodes: vec![Node::Text("Examples".to_string())],
+        },
+        Node::BulletList(items),
+    ]
+}
+
+/// An example from Doxygen's '\example' command
+pub struct ExampleRef {
+    pub refid: String,
+    pub name: String,
+}
+
+/// The examples which refer to each member, keyed by the refid of the member
+pub type MemberExamples = HashMap<String, Vec<ExampleRef>>;
+
+/// Finds the members referred to by the program listings of each example. This loads every example so the result is
+/// cached by the xml loader
+pub fn collect_member_examples(xml_loader: &mut XmlLoader) -> MemberExamples {
+    let mut member_examples = MemberExamples::new();
+
+    let Ok(index) = xml_loader.load_index() else {
+        return member_examples;
+    };
+
+    for compound in index
+        .compound
+        .iter()
+        .filter(|compound| compound.kind == index::CompoundKind::Example)
+    {
+        let Ok(root) = xml_loader.load(&compound.refid) else {
+            continue;
+        };
+        let Some(ref compound_def) = root.compounddef else {
+            continue;
+        };
+
+        for refid in example_refids(compound_def) {
+            member_examples
+                .entry(refid.to_string())
+                .or_default()
+                .push(ExampleRef {
+                    refid: compound.refid.clone(),
+                    name: compound.name.clone(),
+                });
+        }
+    }
+
+    member_examples
+}
+
+/// The refids of everything referred to by the program listings in the example, without duplicates
+fn example_refids(compound_def: &e::CompounddefType) -> BTreeSet<&str> {
+    let description_listings = compound_def
+        .detaileddescription
+        .iter()
+        .flat_map(|description| description.para.iter())
+        .flat_map(|para| para.content.iter())
+        .filter_map(|entry| match entry {
+            e::DocParaTypeItem::DocCmdGroup(e::DocCmdGroup::Programlisting(listing)) => {
+                Some(listing)
+            }
+            _ => None,
+        });
+
+    compound_def
+        .programlisting
+        .iter()
+        .chain(description_listings)
+        .flat_map(|listing| listing.codeline.iter())
+        .flat_map(|code_line| code_line.highlight.iter())
+        .flat_map(|highlight| highlight.content.iter())
+        .filter_map(|entry| match entry {
+            e::HighlightTypeItem::Ref(ref_text) => Some(ref_text.refid.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn render_table_of_contents_type(element: &e::TableofcontentsType) -> Node {
+    let items = element
+        .tocsect
+        .iter()
+        .map(|sect| {
+            let mut nodes = vec![Node::Paragraph(vec![Node::InternalReference {
+                refid: sect.reference.clone(),
+                children: vec![Node::Text(sect.name.clone())],
+            }])];
+
+            nodes.extend(
+                sect.tableofcontents
+                    .iter()
+                    .map(render_table_of_contents_type),
+            );
+
+            Node::ListItem(nodes)
+        })
+        .collect();
+
+    Node::BulletList(items)
+}
+
+/// The entries of the 'sect1' to 'sect4' elements, which only differ in the level of their sub-sections
+enum DocSectEntry<'a> {
+    Title(&'a str),
+    Para(&'a e::DocParaType),
+    Sect(Node),
+    /// The 'internal' entries are only present when Doxygen is configured with INTERNAL_DOCS so we skip them along
+    /// with the whitespace between the elements
+    Skipped,
+}
+
+fn render_doc_sect<'a>(
+    ctx: &Context,
+    id: &str,
+    entries: impl Iterator<Item = DocSectEntry<'a>>,
+) -> Node {
+    let mut title = None;
+    let mut nodes = Vec::new();
+
+    for entry in entries {
+        match entry {
+            DocSectEntry::Title(text) => title = Some(text),
+            DocSectEntry::Para(para) => {
+                nodes.append(&mut render_doc_para_types(ctx, std::slice::from_ref(para)))
+            }
+            DocSectEntry::Sect(sect) => nodes.push(sect),
+            DocSectEntry::Skipped => {}
+        }
+    }
+
+    Node::Section {
+        target: Target {
+            ids: id.to_string(),
+            names: id.to_string(),
+        },
+        title: vec![Node::Text(title.unwrap_or_default().to_string())],
+        nodes,
+    }
+}
+
+fn render_doc_sect1_type(ctx: &Context, element: &e::DocSect1Type) -> Node {
+    let entries = element.content.iter().map(|entry| match entry {
+        e::DocSect1TypeItem::Title(text) => DocSectEntry::Title(text),
+        e::DocSect1TypeItem::Para(para) => DocSectEntry::Para(para),
+        e::DocSect1TypeItem::Sect2(sect) => DocSectEntry::Sect(render_doc_sect2_type(ctx, sect)),
+        e::DocSect1TypeItem::Internal(_) | e::DocSect1TypeItem::Text(_) => DocSectEntry::Skipped,
+    });
+
+    render_doc_sect(ctx, &element.id, entries)
+}
+
+fn render_doc_sect2_type(ctx: &Context, element: &e::DocSect2Type) -> Node {
+    let entries = element.content.iter().map(|entry| match entry {
+        e::DocSect2TypeItem::Title(text) => DocSectEntry::Title(text),
+        e::DocSect2TypeItem::Para(para) => DocSectEntry::Para(para),
+        e::DocSect2TypeItem::Sect3(sect) => DocSectEntry::Sect(render_doc_sect3_type(ctx, sect)),
+        e::DocSect2TypeItem::Internal(_) | e::DocSect2TypeItem::Text(_) => DocSectEntry::Skipped,
+    });
+
+    render_doc_sect(ctx, &element.id, entries)
+}
+
+fn render_doc_sect3_type(ctx: &Context, element: &e::DocSect3Type) -> Node {
+    let entries = element.content.iter().map(|entry| match entry {
+        e::DocSect3TypeItem::Title(text) => DocSectEntry::Title(text),
+        e::DocSect3TypeItem::Para(para) => DocSectEntry::Para(para),
+        e::DocSect3TypeItem::Sect4(sect) => DocSectEntry::Sect(render_doc_sect4_type(ctx, sect)),
+        e::DocSect3TypeItem::Internal(_) | e::DocSect3TypeItem::Text(_) => DocSectEntry::Skipped,
+    });
+
+    render_doc_sect(ctx, &element.id, entries)
+}
+
+fn render_doc_sect4_type(ctx: &Context, element: &e::DocSect4Type) -> Node {
+    let entries = element.content.iter().map(|entry| match entry {
+        e::DocSect4TypeItem::Title(text) => DocSectEntry::Title(text),
+        e::DocSect4TypeItem::Para(para) => DocSectEntry::Para(para),
+        e::DocSect4TypeItem::Internal(_) | e::DocSect4TypeItem::Text(_) => DocSectEntry::Skipped,
+    });
+
+    render_doc_sect(ctx, &element.id, entries)
+}
+
+/// Renders the lines of the body of the function or class as a code block
+fn render_implementation_source(
+    ctx: &Context,
+    refid: &str,
+    location: Option<&e::LocationType>,
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
+    if ctx.implementation_source == ImplementationSource::Hidden {
+        return Vec::new();
+    }
+
+    // Doxygen uses -1 for the body end when it doesn't know where the body ends
+    let Some((file, start, end)) = location.and_then(|location| {
+        let start = location.bodystart.filter(|start| *start > 0)?;
+        let end = location.bodyend.filter(|end| *end >= start)?;
+        let file = location.bodyfile.as_deref().unwrap_or(&location.file);
+        Some((file, start, end))
+    }) else {
+        return Vec::new();
+    };
+
+    let (nodes, highlighted_lines) = match ctx.implementation_source {
+        ImplementationSource::Hidden => return Vec::new(),
+        ImplementationSource::Listing => {
+            render_listing_lines(ctx, refid, file, start, end, xml_loader)
+        }
+        ImplementationSource::File => read_source_lines(ctx, file, start, end, xml_loader)
+            .map(|text| (vec![Node::Text(text)], Vec::new()))
+            .unwrap_or_default(),
+    };
+
+    if nodes.is_empty() {
+        return Vec::new();
+    }
+
+    vec![
+        Node::Rubric {
+            classes: vec!["docleaf-implementation-source-title".to_string()],
+            nodes: vec![Node::Text("Implementation".to_string())],
+        },
+        Node::CodeBlock {
+            language: listing_language(ctx, Some(file)),
+            line_number_start: ctx.listing_line_numbers.then_some(start),
+            highlighted_lines,
+            nodes,
+        },
+    ]
+}
+
+/// Renders the lines from the program listing of the file compound for the file. Doxygen links the line which defines
+/// an entity to that entity so we also return the position of the lines linked to the refid so that they can be
+/// highlighted
+fn render_listing_lines(
+    ctx: &Context,
+    refid: &str,
+    file: &str,
+    start: i32,
+    end: i32,
+    xml_loader: &mut XmlLoader,
+) -> (Vec<Node>, Vec<usize>) {
+    let Some(file_refid) = xml_loader.file_refid(file) else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let Ok(root) = xml_loader.load(&file_refid) else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let Some(listing) = root
+        .compounddef
+        .as_ref()
+        .and_then(|compound_def| compound_def.programlisting.as_ref())
+    else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let code_lines: Vec<_> = listing
+        .codeline
+        .iter()
+        .filter(|code_line| {
+            code_line
+                .lineno
+                .is_some_and(|lineno| lineno >= start && lineno <= end)
+        })
+        .collect();
+
+    let highlighted_lines = code_lines
+        .iter()
+        .enumerate()
+        .filter(|(_, code_line)| code_line.refid.as_deref() == Some(refid))
+        .map(|(index, _)| index + 1)
+        .collect();
+
+    let lines: Vec<Vec<Node>> = code_lines
+        .iter()
+        .map(|code_line| render_code_line_type(ctx, code_line))
+        .collect();
+
+    let nodes = itertools::intersperse(lines, vec![Node::Text("\n".to_string())])
+        .flatten()
+        .collect();
+
+    (nodes, highlighted_lines)
+}
+
+/// Reads the lines, numbered from 1, from the source file under the project root
+fn read_source_lines(
+    ctx: &Context,
+    file: &str,
+    start: i32,
+    end: i32,
+    xml_loader: &mut XmlLoader,
+) -> Option<String> {
+    let path = ctx.project_root.join(file);
+
+    let contents = match xml_loader.read_source(path.clone()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            tracing::warn!("Unable to read source file {}: {err}", path.display());
+            return None;
+        }
+    };
+
+    let lines: Vec<&str> = contents
+        .lines()
+        .skip(usize::try_from(start - 1).ok()?)
+        .take(usize::try_from(end - start + 1).ok()?)
+        .collect();
+
+    Some(lines.join("\n"))
+}
 
-    // Create a new context with the location information if it is there
-    let ctx = &ctx.with_domain(member_def.location.as_ref(), None);
+/// Renders the lists and graphs of the members that the member refers to and is referred to by
+fn render_member_references(ctx: &Context, member_def: &e::MemberdefType) -> Vec<Node> {
+    let mut nodes = Vec::new();
 
-    if let Some(ref description) = member_def.briefdescription {
-        content_nodes.append(&mut render_description(ctx, description));
+    if ctx.references.contains(&ReferenceKind::References) && !member_def.references.is_empty() {
+        nodes.append(&mut render_reference_refs(
+            "References",
+            &member_def.references,
+        ));
     }
 
-    if let Some(ref description) = member_def.detaileddescription {
-        content_nodes.append(&mut render_description(ctx, description));
+    if ctx.references.contains(&ReferenceKind::ReferencedBy) && !member_def.referencedby.is_empty()
+    {
+        nodes.append(&mut render_reference_refs(
+            "Referenced by",
+            &member_def.referencedby,
+        ));
     }
 
-    let ids = member_def.id.clone();
-    let names = member_def.id.clone();
-    let target = Target { ids, names };
+    // Only functions make calls so graphs of other kinds of members wouldn't be meaningful
+    if member_def.kind != e::DoxMemberKind::Function {
+        return nodes;
+    }
 
-    let signature_line;
+    let label = member_def
+        .qualifiedname
+        .as_deref()
+        .unwrap_or(&member_def.name);
 
-    let basic_signature_line = |target| {
-        vec![
-            Node::Target(target),
-            Node::DescSignatureKeyword(vec![Node::Text(name)]),
-            Node::DescSignatureSpace,
-            Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
-        ]
-    };
+    if ctx.references.contains(&ReferenceKind::CallGraph) && !member_def.references.is_empty() {
+        let graph = dot::reference_graph(&member_def.id, label, &member_def.references, false);
+        nodes.append(&mut render_graph_type(
+            ctx,
+            "Call graph",
+            &member_def.id,
+            RankDir::LeftToRight,
+            &graph,
+        ));
+    }
 
-    match member_def.kind {
-        e::DoxMemberKind::Enum => {
-            // Pass through the name or the id if there is no name. The '@' is used by Sphinx to
-            // determine anonymous enums
-            let enum_name = if member_def.name.is_empty() {
-                format!("@{}", member_def.id)
-            } else {
-                member_def.name.clone()
-            };
+    if ctx.references.contains(&ReferenceKind::CallerGraph) && !member_def.referencedby.is_empty() {
+        let graph = dot::reference_graph(&member_def.id, label, &member_def.referencedby, true);
+        nodes.append(&mut render_graph_type(
+            ctx,
+            "Caller graph",
+            &member_def.id,
+            RankDir::RightToLeft,
+            &graph,
+        ));
+    }
 
-            content_nodes.append(
-                &mut member_def
-                    .enumvalue
-                    .iter()
-                    .map(|element| render_enum_value(ctx, &enum_name, element))
-                    .collect(),
-            );
+    nodes
+}
 
-            // Early exit if there is domain information for rendering this entry
-            if let Some(ref domain) = ctx.domain {
-                return vec![Node::DomainEntry(Box::new(DomainEntry {
-                    domain: domain.clone(),
-                    type_: DomainEntryType::Enum,
-                    target,
-                    declaration: text::render_member_def(domain, member_def),
-                    location: member_def
-                        .location
-                        .as_ref()
-                        .and_then(|loc| Location::from(&ctx.project_root, loc)),
-                    content: content_nodes,
-                }))];
-            }
+/// Renders a list linking to each of the referenced, or referencing, members
+fn render_reference_refs(title: &str, references: &[e::ReferenceType]) -> Vec<Node> {
+    let members = references
+        .iter()
+        .map(|reference| (reference.refid.as_str(), reference.content.clone()));
 
-            signature_line = basic_signature_line(target);
+    render_member_link_list("docleaf-references-title", title, members)
+}
+
+/// Renders the description of the first member up the chain of reimplemented members which has one, in the manner of
+/// Doxygen's INHERIT_DOCS setting
+fn render_inherited_description(
+    ctx: &Context,
+    member_def: &e::MemberdefType,
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
+    let mut seen = vec![member_def.id.clone()];
+    let mut next = member_def
+        .reimplements
+        .first()
+        .map(|reimplement| reimplement.refid.clone());
+
+    while let Some(refid) = next.take() {
+        if seen.contains(&refid) {
+            break;
         }
-        e::DoxMemberKind::Function => {
-            // Early exit if there is domain information for rendering this entry
-            if let Some(ref domain) = ctx.domain {
-                return vec![Node::DomainEntry(Box::new(DomainEntry {
-                    domain: domain.clone(),
-                    type_: DomainEntryType::Function,
-                    target,
-                    declaration: text::render_member_def(domain, member_def),
-                    location: member_def
-                        .location
-                        .as_ref()
-                        .and_then(|loc| Location::from(&ctx.project_root, loc)),
-                    content: content_nodes,
-                }))];
+
+        let root = match xml_loader.load(compound_refid(&refid)) {
+            Ok(root) => root,
+            Err(err) => {
+                tracing::warn!("Unable to load reimplemented member {refid}: {err}");
+                break;
             }
+        };
 
-            let parameter_list_items = member_def
-                .param
-                .iter()
-                .map(|param| {
-                    let mut param_contents = Vec::new();
+        let Some(base_def) = find_member_def(root.as_ref(), &refid) else {
+            break;
+        };
 
-                    match (&param.type_, &param.declname) {
-                        (Some(ref type_), Some(ref declname)) => {
-                            param_contents.append(&mut render_linked_text_type(ctx, type_));
-                            param_contents.push(Node::DescSignatureSpace);
-                            param_contents.push(Node::DescSignatureName(declname.clone()));
-                        }
-                        (Some(ref type_), None) => {
-                            param_contents.append(&mut render_linked_text_type(ctx, type_));
-                        }
-                        (None, Some(ref declname)) => {
-                            param_contents.push(Node::DescSignatureName(declname.clone()));
-                        }
-                        (None, None) => {}
-                    };
+        let mut nodes = Vec::new();
 
-                    Node::DescParameter(param_contents)
-                })
-                .collect();
+        if let Some(ref description) = base_def.briefdescription {
+            nodes.append(&mut render_description(ctx, description));
+        }
 
-            match member_def.type_ {
-                Some(ref type_) => {
-                    signature_line = vec![
-                        Node::Target(target),
-                        Node::DescSignatureKeyword(render_linked_text_type(ctx, type_)),
-                        Node::DescSignatureSpace,
-                        Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
-                        Node::DescParameterList(parameter_list_items),
-                    ];
-                }
-                None => {
-                    signature_line = vec![
-                        Node::Target(target),
-                        Node::DescName(Box::new(Node::DescSignatureName(member_def.name.clone()))),
-                        Node::DescParameterList(parameter_list_items),
-                    ];
-                }
-            }
+        if let Some(ref description) = base_def.detaileddescription {
+            nodes.append(&mut render_description(ctx, description));
         }
-        e::DoxMemberKind::Define => {
-            // Early exit if there is domain information for rendering this entry
-            if let Some(ref domain) = ctx.domain {
-                return vec![Node::DomainEntry(Box::new(DomainEntry {
-                    domain: domain.clone(),
-                    type_: DomainEntryType::Define,
-                    target,
-                    declaration: text::render_member_def(domain, member_def),
-                    location: member_def
-                        .location
-                        .as_ref()
-                        .and_then(|loc| Location::from(&ctx.project_root, loc)),
-                    content: content_nodes,
-                }))];
-            }
 
-            signature_line = basic_signature_line(target);
+        if !nodes.is_empty() {
+            return nodes;
         }
-        e::DoxMemberKind::Variable => {
-            // Don't return any nodes for an anonymous union member as we don't have a good representation for it
-            // and it ends up confusing Sphinx domains a bit
-            if variable_member_def_is_anonymous_union(member_def) {
-                return vec![];
-            }
 
-            // Don't return any nodes if the variable is actually a struct inside a union and the struct's id matches
-            // the compound id that we're inside as this represents a confused, perhaps failure, case in Doxygen where
-            // this are not linked up properly and rendering the resulting entry wouldn't make sense
-            if compound_kind == &e::DoxCompoundKind::Union
-                && variable_member_def_is_struct_matching_id(member_def, compound_id)
-            {
-                return vec![];
-            }
+        seen.push(refid);
+        next = base_def
+            .reimplements
+            .first()
+            .map(|reimplement| reimplement.refid.clone());
+    }
 
-            // Early exit if there is domain information for rendering this entry
-            if let Some(ref domain) = ctx.domain {
-                return vec![Node::DomainEntry(Box::new(DomainEntry {
-                    domain: domain.clone(),
-                    // All variables in the memberdef context are just members of the compound
-                    // TODO: This might not be the case for variables in 'file' compounds
-                    type_: DomainEntryType::Member,
-                    target,
-                    declaration: text::render_member_def(domain, member_def),
-                    location: member_def
-                        .location
-                        .as_ref()
-                        .and_then(|loc| Location::from(&ctx.project_root, loc)),
-                    content: content_nodes,
-                }))];
-            }
+    Vec::new()
+}
 
-            signature_line = basic_signature_line(target);
-        }
-        e::DoxMemberKind::Typedef => {
-            // Early exit if there is domain information for rendering this entry
-            if let Some(ref domain) = ctx.domain {
-                return vec![Node::DomainEntry(Box::new(DomainEntry {
-                    domain: domain.clone(),
-                    type_: DomainEntryType::Typedef,
-                    target,
-                    declaration: text::render_member_def(domain, member_def),
-                    location: member_def
-                        .location
-                        .as_ref()
-                        .and_then(|loc| Location::from(&ctx.project_root, loc)),
-                    content: content_nodes,
-                }))];
-            }
+fn find_member_def<'a>(root: &'a e::DoxygenType, refid: &str) -> Option<&'a e::MemberdefType> {
+    root.compounddef
+        .as_ref()?
+        .sectiondef
+        .iter()
+        .find_map(|section_def| {
+            section_def
+                .memberdef
+                .iter()
+                .find(|member_def| member_def.id == refid)
+        })
+}
 
-            signature_line = basic_signature_line(target);
-        }
-        _ => {
-            signature_line = basic_signature_line(target);
-        }
-    };
+/// Renders a list linking to each of the reimplemented, or reimplementing, members
+fn render_reimplement_refs(
+    title: &str,
+    reimplements: &[e::ReimplementType],
+    xml_loader: &mut XmlLoader,
+) -> Vec<Node> {
+    // The compound names come from the index, which is loaded once and cached, rather than from the xml of each
+    // compound
+    let index = xml_loader.load_index().ok();
+
+    let members = reimplements.iter().map(|reimplement| {
+        (
+            reimplement.refid.as_str(),
+            reimplement_name(reimplement, index.as_deref()),
+        )
+    });
 
-    let content = Node::DescContent(content_nodes);
+    render_member_link_list("docleaf-reimplements-title", title, members)
+}
 
-    vec![Node::Desc {
-        object_type: text::render_member_kind(&member_def.kind).to_string(),
-        domain: ctx.domain.clone(),
-        signature_lines: vec![Node::DescSignature(
-            SignatureType::MultiLine,
-            vec![Node::DescSignatureLine(signature_line)],
-        )],
-        content: Box::new(content),
-    }]
+/// Renders a titled list of links to members, given by refid and name. The members might be in compounds that
+/// aren't included in the Sphinx documentation so the links are optional
+fn render_member_link_list<'a>(
+    class: &str,
+    title: &str,
+    members: impl Iterator<Item = (&'a str, String)>,
+) -> Vec<Node> {
+    let items = members
+        .map(|(refid, name)| {
+            Node::ListItem(vec![Node::Paragraph(vec![Node::OptionalReference {
+                refid: refid.to_string(),
+                children: vec![Node::Literal(vec![Node::Text(name)])],
+            }])])
+        })
+        .collect();
+
+    vec![
+        Node::Rubric {
+            classes: vec![class.to_string()],
+            nodes: vec![Node::Text(title.to_string())],
+        },
+        Node::BulletList(items),
+    ]
+}
+
+/// The xml only provides the member name so we qualify it with the name of the compound that contains the member
+/// to make clear which class it belongs to
+fn reimplement_name(
+    reimplement: &e::ReimplementType,
+    index: Option<&index::DoxygenType>,
+) -> String {
+    let compound_refid = compound_refid(&reimplement.refid);
+    let compound_name = index.and_then(|index| {
+        index
+            .compound
+            .iter()
+            .find(|compound| compound.refid == compound_refid)
+            .map(|compound| compound.name.as_str())
+    });
+
+    match compound_name {
+        Some(compound_name) => format!("{compound_name}::{}", reimplement.content),
+        None => reimplement.content.clone(),
+    }
 }
 
-fn is_upper_snake_case(str: &str) -> bool {
-    str.chars()
-        .all(|char| char.is_ascii_uppercase() || char == '_')
+/// Picks the file and line for the entity from the location. Doxygen only provides a line for some entities so we
+/// fall back to the start of the body or to the declaration. When an entity has a separate declaration, eg. a
+/// function declared in a header, then the 'file' and 'line' refer to the definition
+fn render_location(ctx: &Context, location: Option<&e::LocationType>) -> Option<Location> {
+    let location = location?;
+
+    let line = location.line.map(|line| (location.file.as_str(), line));
+    let body = location.bodystart.filter(|line| *line > 0).map(|line| {
+        let file = location.bodyfile.as_deref().unwrap_or(&location.file);
+        (file, line)
+    });
+    let declaration = location
+        .declline
+        .map(|line| (location.declfile.as_deref().unwrap_or(&location.file), line));
+
+    let (file, line) = match ctx.source_location {
+        SourceLocation::Definition => line.or(body).or(declaration),
+        SourceLocation::Declaration => declaration.or(line).or(body),
+    }?;
+
+    Some(Location::new(&ctx.project_root, file, line))
 }
 
 /// Returns true if the provided member_def represents an anonymous union to the best of our knowledge
@@ -719,7 +2474,13 @@ fn member_kind_name(member_kind: &e::DoxMemberKind) -> String {
     }
 }
 
-pub fn render_enum_value(ctx: &Context, enum_name: &str, enum_value: &e::EnumvalueType) -> Node {
+/// Enumerators have no location of their own in the xml so we use the location of the enum
+pub fn render_enum_value(
+    ctx: &Context,
+    enum_name: &str,
+    location: Option<&e::LocationType>,
+    enum_value: &e::EnumvalueType,
+) -> Node {
     let mut content_nodes = Vec::new();
 
     if let Some(ref description) = enum_value.briefdescription {
@@ -739,7 +2500,7 @@ pub fn render_enum_value(ctx: &Context, enum_name: &str, enum_value: &e::Enumval
             type_: DomainEntryType::Enumerator,
             target,
             declaration: text::render_enum_value(domain, enum_name, enum_value),
-            location: None,
+            location: render_location(ctx, location),
             content: content_nodes,
         }))
     } else {
@@ -761,8 +2522,12 @@ pub fn render_enum_value(ctx: &Context, enum_name: &str, enum_value: &e::Enumval
 
 /// We treat certain nodes as special, like the parameter lists.
 fn render_description(ctx: &Context, element: &e::DescriptionType) -> Vec<Node> {
-    let cat_nodes: Vec<_> = element
-        .para
+    render_doc_para_types(ctx, &element.para)
+}
+
+/// Renders a sequence of 'para' elements as found in descriptions and other block level elements like blockquotes
+fn render_doc_para_types(ctx: &Context, paras: &[e::DocParaType]) -> Vec<Node> {
+    let cat_nodes: Vec<_> = paras
         .iter()
         // Render the para node contents and then lift all the special nodes (list paramater lists) out of the para
         // output and group the rest under Paragraph nodes. This allows us to manage the special nodes whilst still
@@ -956,6 +2721,10 @@ impl ToNodes for Vec<CategorizedNode> {
 // As we need to treat the ParameterList (and maybe some other nodes) as a special case we render into the
 // CategorizedNode type so that we can separate the parameter lists, etc, further up in the stack if needed
 fn render_doc_cmd_group(ctx: &Context, element: &e::DocCmdGroup) -> Option<CategorizedNode> {
+    if ctx.filter.skip_doc_cmd(element) {
+        return None;
+    }
+
     match element {
         e::DocCmdGroup::DocTitleCmdGroup(element) => {
             render_doc_title_cmd_group(ctx, element).map(CategorizedNode::Node)
@@ -985,6 +2754,21 @@ fn render_doc_cmd_group(ctx: &Context, element: &e::DocCmdGroup) -> Option<Categ
         e::DocCmdGroup::Table(element) => {
             Some(CategorizedNode::Node(render_doc_table_type(ctx, element)))
         }
+        e::DocCmdGroup::Variablelist(element) => Some(CategorizedNode::Block(
+            render_doc_variable_list_type(ctx, element),
+        )),
+        e::DocCmdGroup::Blockquote(element) => Some(CategorizedNode::Block(Node::BlockQuote(
+            render_doc_para_types(ctx, &element.para),
+        ))),
+        e::DocCmdGroup::Parblock(element) => Some(CategorizedNode::Block(Node::Compound(
+            render_doc_para_types(ctx, &element.para),
+        ))),
+        e::DocCmdGroup::Details(element) => Some(CategorizedNode::Block(render_doc_details_type(
+            ctx, element,
+        ))),
+        e::DocCmdGroup::Toclist(element) => Some(CategorizedNode::Block(render_doc_toc_list_type(
+            ctx, element,
+        ))),
         // TODO: Change to panic
         _ => {
             tracing::error!("Unhandled DocCmdGroup node: {element:?} in render_doc_cmd_group");
@@ -993,6 +2777,34 @@ fn render_doc_cmd_group(ctx: &Context, element: &e::DocCmdGroup) -> Option<Categ
     }
 }
 
+fn render_doc_toc_list_type(ctx: &Context, element: &e::DocTocListType) -> Node {
+    let items = element
+        .tocitem
+        .iter()
+        .map(|item| {
+            let mut nodes = Vec::new();
+
+            for entry in item.content.iter() {
+                match entry {
+                    e::DocTocItemTypeItem::DocTitleCmdGroup(ref content) => {
+                        if let Some(node) = render_doc_title_cmd_group(ctx, content) {
+                            nodes.push(node)
+                        }
+                    }
+                    e::DocTocItemTypeItem::Text(text) => nodes.push(Node::Text(text.clone())),
+                }
+            }
+
+            Node::ListItem(vec![Node::Paragraph(vec![Node::InternalReference {
+                refid: item.id.clone(),
+                children: nodes,
+            }])])
+        })
+        .collect();
+
+    Node::BulletList(items)
+}
+
 fn render_doc_table_type(ctx: &Context, element: &e::DocTableType) -> Node {
     tracing::debug!("render_doc_table_type");
     let rows: Vec<_> = element
@@ -1062,6 +2874,37 @@ fn render_doc_entry_type(ctx: &Context, element: &e::DocEntryType) -> TableCell
     }
 }
 
+fn render_doc_variable_list_type(ctx: &Context, element: &e::DocVariableListType) -> Node {
+    // The varlistentry and listitem elements alternate in the xml so we pair them back up here
+    let items = element
+        .varlistentry
+        .iter()
+        .zip(element.listitem.iter())
+        .map(|(entry, item)| {
+            Node::DefinitionListItem(vec![
+                Node::Term(render_doc_title_type(ctx, &entry.term)),
+                Node::Definition(render_doc_para_types(ctx, &item.para)),
+            ])
+        })
+        .collect();
+
+    Node::DefinitionList(items)
+}
+
+fn render_doc_details_type(ctx: &Context, element: &e::DocDetailsType) -> Node {
+    let mut nodes = Vec::new();
+
+    if let Some(ref summary) = element.summary {
+        nodes.push(Node::DetailsSummary(
+            render_doc_markup_type(ctx, summary).into_nodes(),
+        ));
+    }
+
+    nodes.append(&mut render_doc_para_types(ctx, &element.para));
+
+    Node::Details(nodes)
+}
+
 fn render_doc_xref_sect_type(ctx: &Context, element: &e::DocXRefSectType) -> Node {
     Node::Desc {
         domain: ctx.domain.clone(),
@@ -1133,7 +2976,22 @@ fn render_listing_type(ctx: &Context, element: &e::ListingType) -> Node {
         .flat_map(|vec| vec.into_iter())
         .collect();
 
-    Node::LiteralBlock(nodes)
+    // Listings from '\code' blocks have no line numbers so we only number the listings of files
+    let line_number_start = if ctx.listing_line_numbers {
+        element
+            .codeline
+            .first()
+            .and_then(|code_line| code_line.lineno)
+    } else {
+        None
+    };
+
+    Node::CodeBlock {
+        language: listing_language(ctx, element.filename.as_deref()),
+        line_number_start,
+        highlighted_lines: Vec::new(),
+        nodes,
+    }
 }
 
 fn render_code_line_type(ctx: &Context, element: &e::CodelineType) -> Vec<Node> {
@@ -1144,24 +3002,50 @@ fn render_code_line_type(ctx: &Context, element: &e::CodelineType) -> Vec<Node>
         .collect()
 }
 
+/// Renders the text of the listing with the references that Doxygen links kept as links so that the code block
+/// becomes a parsed literal. Runs of text and whitespace are merged so that we don't create a node per space
 fn render_highlight_type(ctx: &Context, element: &e::HighlightType) -> Vec<Node> {
     let mut nodes = Vec::new();
+    let mut text = String::new();
 
     for entry in element.content.iter() {
         match entry {
-            e::HighlightTypeItem::Sp(ref content) => nodes.push(render_sp_type(ctx, content)),
+            e::HighlightTypeItem::Sp(ref content) => text.push(render_sp_type(ctx, content)),
             e::HighlightTypeItem::Ref(ref content) => {
-                nodes.push(render_ref_text_type(ctx, content))
+                if !text.is_empty() {
+                    nodes.push(Node::Text(std::mem::take(&mut text)));
+                }
+                nodes.push(render_listing_ref_text_type(ctx, content))
             }
-            e::HighlightTypeItem::Text(text) => nodes.push(Node::Text(text.clone())),
+            e::HighlightTypeItem::Text(content) => text.push_str(content),
         }
     }
 
+    if !text.is_empty() {
+        nodes.push(Node::Text(text));
+    }
+
     nodes
 }
 
-fn render_sp_type(_ctx: &Context, _elementt: &e::SpType) -> Node {
-    Node::Text(" ".to_string())
+/// Doxygen writes a '<sp/>' for each space and gives the character code in the value attribute for any other
+/// whitespace, like tabs
+fn render_sp_type(_ctx: &Context, element: &e::SpType) -> char {
+    element
+        .value
+        .and_then(|value| u32::try_from(value).ok())
+        .and_then(char::from_u32)
+        .unwrap_or(' ')
+}
+
+/// Links in listings contain only the plain text so that they are presented in the same manner as the surrounding
+/// code
+fn render_listing_ref_text_type(_ctx: &Context, ref_text_type: &e::RefTextType) -> Node {
+    // Listings link every symbol that Doxygen knows about, many of which aren't included in the Sphinx documentation
+    Node::OptionalReference {
+        refid: ref_text_type.refid.clone(),
+        children: vec![Node::Text(ref_text_type.content.clone())],
+    }
 }
 
 enum ListType {
@@ -1221,45 +3105,61 @@ fn render_doc_list_item_type(ctx: &Context, element: &e::DocListItemType) -> Nod
     Node::ListItem(contents)
 }
 
-/// TODO: Incomplete - just renders the para blocks at the moment
+/// Simple sections which are closer to metadata (return values, authors, versions, etc) are lifted into the field
+/// list whilst the others are rendered in place as admonitions
 fn render_doc_simple_sect_type(ctx: &Context, element: &e::DocSimpleSectType) -> CategorizedNode {
-    let nodes = element
-        .para
-        .iter()
-        .map(|element| Node::Paragraph(render_doc_para_type(ctx, element).into_nodes()))
-        .collect();
+    let nodes = render_doc_para_types(ctx, element.para.as_slice());
 
     match element.kind {
-        e::DoxSimpleSectKind::Return => {
-            CategorizedNode::FieldListEntry("Returns".into(), Node::Container(nodes))
-        }
+        e::DoxSimpleSectKind::Return => simple_sect_field("Returns", nodes),
+        e::DoxSimpleSectKind::Author => simple_sect_field("Author", nodes),
+        e::DoxSimpleSectKind::Authors => simple_sect_field("Authors", nodes),
+        e::DoxSimpleSectKind::Version => simple_sect_field("Version", nodes),
+        e::DoxSimpleSectKind::Since => simple_sect_field("Since", nodes),
+        e::DoxSimpleSectKind::Date => simple_sect_field("Date", nodes),
+        e::DoxSimpleSectKind::Copyright => simple_sect_field("Copyright", nodes),
         e::DoxSimpleSectKind::Note => CategorizedNode::Node(Node::Note(nodes)),
         e::DoxSimpleSectKind::See => CategorizedNode::Node(Node::SeeAlso(nodes)),
         e::DoxSimpleSectKind::Warning => CategorizedNode::Node(Node::Warning(nodes)),
-        e::DoxSimpleSectKind::Author
-        | e::DoxSimpleSectKind::Authors
-        | e::DoxSimpleSectKind::Version
-        | e::DoxSimpleSectKind::Since
-        | e::DoxSimpleSectKind::Date
-        | e::DoxSimpleSectKind::Pre
-        | e::DoxSimpleSectKind::Post
-        | e::DoxSimpleSectKind::Copyright
-        | e::DoxSimpleSectKind::Invariant
-        | e::DoxSimpleSectKind::Remark
-        | e::DoxSimpleSectKind::Attention
-        | e::DoxSimpleSectKind::Par
-        | e::DoxSimpleSectKind::Rcs => CategorizedNode::Node(Node::Container(nodes)),
+        e::DoxSimpleSectKind::Attention => CategorizedNode::Node(Node::Attention(nodes)),
+        e::DoxSimpleSectKind::Pre => simple_sect_admonition("Precondition", nodes),
+        e::DoxSimpleSectKind::Post => simple_sect_admonition("Postcondition", nodes),
+        e::DoxSimpleSectKind::Invariant => simple_sect_admonition("Invariant", nodes),
+        e::DoxSimpleSectKind::Remark => simple_sect_admonition("Remark", nodes),
+        // Paragraphs with titles ('\par Title') are rendered as admonitions using the title whilst those without a
+        // title are just a continuation of the description so we render the paragraphs in place
+        e::DoxSimpleSectKind::Par | e::DoxSimpleSectKind::Rcs => {
+            let title = element
+                .title
+                .as_ref()
+                .map(|title| render_doc_title_type(ctx, title))
+                .unwrap_or_default();
+
+            if title.is_empty() || all_white_space(&title) {
+                CategorizedNode::Node(Node::Container(nodes))
+            } else {
+                CategorizedNode::Node(Node::Admonition { title, nodes })
+            }
+        }
     }
 }
 
+fn simple_sect_field(name: &str, nodes: Vec<Node>) -> CategorizedNode {
+    CategorizedNode::FieldListEntry(name.to_string(), Node::Container(nodes))
+}
+
+fn simple_sect_admonition(title: &str, nodes: Vec<Node>) -> CategorizedNode {
+    CategorizedNode::Node(Node::Admonition {
+        title: vec![Node::Text(title.to_string())],
+        nodes,
+    })
+}
+
 fn render_doc_param_list_type(ctx: &Context, element: &e::DocParamListType) -> CategorizedNode {
     let mut nodes = Vec::new();
 
     for item in element.parameteritem.iter() {
-        let mut contents = vec![Node::LiteralStrong(render_doc_param_name_list(
-            ctx,
-            &item.parameternamelist,
-        ))];
+        let mut contents = render_doc_param_name_list(ctx, &element.kind, &item.parameternamelist);
 
         contents.push(Node::Text(" - ".to_string()));
 
@@ -1282,12 +3182,152 @@ fn render_doc_param_list_type(ctx: &Context, element: &e::DocParamListType) -> C
     CategorizedNode::FieldListEntry(name, Node::BulletList(nodes))
 }
 
-fn render_doc_param_name_list(ctx: &Context, element: &e::DocParamNameList) -> Vec<Node> {
+/// Renders the names, directions and types for a parameter list entry according to the parameter format setting
+fn render_doc_param_name_list(
+    ctx: &Context,
+    kind: &e::DoxParamListKind,
+    element: &e::DocParamNameList,
+) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    match ctx.parameter_format {
+        ParameterFormat::Names => {
+            nodes.push(Node::LiteralStrong(
+                element
+                    .parametername
+                    .iter()
+                    .flat_map(|name| render_doc_param_name(ctx, name))
+                    .collect(),
+            ));
+        }
+        ParameterFormat::Annotated => {
+            let type_ = render_doc_param_name_list_type(ctx, kind, element);
+
+            for (index, name) in element.parametername.iter().enumerate() {
+                if index != 0 {
+                    nodes.push(Node::Text(", ".to_string()));
+                }
+                if let Some(ref direction) = name.direction {
+                    nodes.push(Node::Text(format!("[{}] ", param_direction(direction))));
+                }
+                nodes.push(Node::LiteralStrong(render_doc_param_name(ctx, name)));
+            }
+
+            if let Some(type_) = type_ {
+                nodes.push(Node::Text(" (".to_string()));
+                nodes.push(Node::Literal(type_));
+                nodes.push(Node::Text(")".to_string()));
+            }
+        }
+        ParameterFormat::Sphinx => {
+            let type_ = render_doc_param_name_list_type(ctx, kind, element);
+
+            // Names which share a direction are listed together, otherwise each name is given its own direction
+            let first_direction = element.parametername.first().map(|name| &name.direction);
+            let shared_direction = element
+                .parametername
+                .iter()
+                .all(|name| Some(&name.direction) == first_direction);
+
+            if shared_direction {
+                let names = element
+                    .parametername
+                    .iter()
+                    .map(|name| render_doc_param_name(ctx, name));
+                let names = itertools::intersperse(names, vec![Node::Text(", ".to_string())])
+                    .flatten()
+                    .collect();
+
+                nodes.append(&mut render_sphinx_param_entry(
+                    names,
+                    type_,
+                    first_direction.and_then(Option::as_ref),
+                ));
+            } else {
+                for (index, name) in element.parametername.iter().enumerate() {
+                    if index != 0 {
+                        nodes.push(Node::Text(", ".to_string()));
+                    }
+                    nodes.append(&mut render_sphinx_param_entry(
+                        render_doc_param_name(ctx, name),
+                        type_.clone(),
+                        name.direction.as_ref(),
+                    ));
+                }
+            }
+        }
+    }
+
+    nodes
+}
+
+/// Renders the names for a parameter list entry followed by the type and direction in the style of Sphinx's ':param:'
+/// fields, eg. 'name (type, in)'
+fn render_sphinx_param_entry(
+    names: Vec<Node>,
+    type_: Option<Vec<Node>>,
+    direction: Option<&e::DoxParamDir>,
+) -> Vec<Node> {
+    let mut nodes = vec![Node::Strong(names)];
+
+    let direction =
+        direction.map(|direction| vec![Node::Text(param_direction(direction).to_string())]);
+
+    let annotations: Vec<_> = [type_, direction]
+        .into_iter()
+        .flatten()
+        .map(Node::Emphasis)
+        .collect();
+
+    if !annotations.is_empty() {
+        nodes.push(Node::Text(" (".to_string()));
+        nodes.extend(itertools::intersperse(
+            annotations,
+            Node::Text(", ".to_string()),
+        ));
+        nodes.push(Node::Text(")".to_string()));
+    }
+
+    nodes
+}
+
+/// Returns the type for the parameter list entry, preferring the explicit 'parametertype' entries and falling back to
+/// the types from the function declaration for regular parameters
+fn render_doc_param_name_list_type(
+    ctx: &Context,
+    kind: &e::DoxParamListKind,
+    element: &e::DocParamNameList,
+) -> Option<Vec<Node>> {
+    if !element.parametertype.is_empty() {
+        let types = element
+            .parametertype
+            .iter()
+            .map(|type_| render_doc_param_type(ctx, type_));
+
+        return Some(
+            itertools::intersperse(types, vec![Node::Text(" | ".to_string())])
+                .flatten()
+                .collect(),
+        );
+    }
+
+    if kind != &e::DoxParamListKind::Param {
+        return None;
+    }
+
     element
         .parametername
-        .iter()
-        .flat_map(|element| render_doc_param_name(ctx, element))
-        .collect()
+        .first()
+        .and_then(|name| ctx.parameter_types.get(&doc_param_name_text(name)))
+        .cloned()
+}
+
+fn param_direction(direction: &e::DoxParamDir) -> &'static str {
+    match direction {
+        e::DoxParamDir::In => "in",
+        e::DoxParamDir::Out => "out",
+        e::DoxParamDir::Inout => "in,out",
+    }
 }
 
 // TODO: Create macros or abstraction for this Ref + Text pattern
@@ -1304,6 +3344,34 @@ fn render_doc_param_name(ctx: &Context, element: &e::DocParamName) -> Vec<Node>
     nodes
 }
 
+/// Plain text version of the parameter name for looking up information by name
+fn doc_param_name_text(element: &e::DocParamName) -> String {
+    element
+        .content
+        .iter()
+        .map(|entry| match entry {
+            e::DocParamNameItem::Ref(content) => content.content.as_str(),
+            e::DocParamNameItem::Text(text) => text.as_str(),
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+// TODO: Create macros or abstraction for this Ref + Text pattern
+fn render_doc_param_type(ctx: &Context, element: &e::DocParamType) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    for entry in element.content.iter() {
+        match entry {
+            e::DocParamTypeItem::Ref(ref content) => nodes.push(render_ref_text_type(ctx, content)),
+            e::DocParamTypeItem::Text(text) => nodes.push(Node::Text(text.clone())),
+        }
+    }
+
+    nodes
+}
+
 // TODO: Create macros or abstraction for this Ref + Text pattern
 fn render_linked_text_type(ctx: &Context, linked_text_type: &e::LinkedTextType) -> Vec<Node> {
     let mut nodes = Vec::new();
@@ -1352,6 +3420,10 @@ fn render_doc_title_cmd_group(
     doc_title_cmd_group: &e::DocTitleCmdGroup,
 ) -> Option<Node> {
     tracing::debug!("render_doc_title_cmd_group {doc_title_cmd_group:?}");
+    if ctx.filter.skip_doc_title_cmd(doc_title_cmd_group) {
+        return None;
+    }
+
     match doc_title_cmd_group {
         e::DocTitleCmdGroup::Ref(element) => Some(render_doc_ref_text_type(ctx, element)),
         e::DocTitleCmdGroup::Bold(element) => Some(Node::Strong(
@@ -1366,13 +3438,13 @@ fn render_doc_title_cmd_group(
         // This might not be the correct way to handle it but there isn't a reStructuredText line break node
         e::DocTitleCmdGroup::Linebreak => Some(Node::Text("\n".to_string())),
         e::DocTitleCmdGroup::Htmlonly(element) => {
-            if ctx.skip.contains(&Skip::XmlNode("htmlonly".to_string())) {
-                None
-            } else {
-                Some(Node::HtmlOnly(vec![Node::RawHtml(element.content.clone())]))
-            }
+            Some(Node::HtmlOnly(vec![Node::RawHtml(element.content.clone())]))
         }
         e::DocTitleCmdGroup::Ulink(element) => Some(render_doc_url_link(ctx, element)),
+        e::DocTitleCmdGroup::Anchor(element) => Some(Node::Target(Target {
+            ids: element.id.clone(),
+            names: element.id.clone(),
+        })),
 
         // Simple characters
         // Use unicode sequence as rustfmt doesn't seem to like the en-dash character
@@ -1407,6 +3479,23 @@ fn render_doc_title_cmd_group(
     }
 }
 
+fn render_doc_title_type(ctx: &Context, element: &e::DocTitleType) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    for entry in element.content.iter() {
+        match entry {
+            e::DocTitleTypeItem::DocTitleCmdGroup(ref content) => {
+                if let Some(node) = render_doc_title_cmd_group(ctx, content) {
+                    nodes.push(node)
+                }
+            }
+            e::DocTitleTypeItem::Text(text) => nodes.push(Node::Text(text.clone())),
+        }
+    }
+
+    nodes
+}
+
 fn render_doc_markup_type(ctx: &Context, element: &e::DocMarkupType) -> Vec<CategorizedNode> {
     let mut nodes = Vec::new();
 
@@ -1445,3 +3534,958 @@ fn render_doc_url_link(ctx: &Context, element: &e::DocUrlLink) -> Node {
         children: nodes,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    /// Parses the paragraphs from the detailed description of a class compound
+    fn parse_paras(paras: &str) -> Vec<e::DocParaType> {
+        let xml = format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="class_a" kind="class" language="C++" prot="public">
+                    <compoundname>A</compoundname>
+                    <detaileddescription>{paras}</detaileddescription>
+                </compounddef>
+            </doxygen>"#
+        );
+
+        let root = e::parse(&xml).unwrap();
+        root.compounddef.unwrap().detaileddescription.unwrap().para
+    }
+
+    /// The text of a list of Text, Paragraph, Literal and OptionalReference nodes
+    fn text(nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Text(text) => text.clone(),
+                Node::Paragraph(nodes) | Node::Literal(nodes) => text(nodes),
+                Node::OptionalReference { children, .. } => text(children),
+                node => panic!("Unexpected node: {node:?}"),
+            })
+            .collect()
+    }
+
+    /// Parses the memberdef elements from a class compound
+    fn parse_member_defs(members: &[&str]) -> Vec<e::MemberdefType> {
+        let xml = format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="class_a" kind="class" language="C++" prot="public">
+                    <compoundname>A</compoundname>
+                    <sectiondef kind="public-func">{}</sectiondef>
+                </compounddef>
+            </doxygen>"#,
+            members.join("")
+        );
+
+        let root = e::parse(&xml).unwrap();
+        root.compounddef
+            .unwrap()
+            .sectiondef
+            .remove(0)
+            .memberdef
+            .into_vec()
+    }
+
+    fn member(kind: &str, name: &str, location: Option<(&str, i32)>) -> String {
+        let location = location
+            .map(|(file, line)| format!(r#"<location file="{file}" line="{line}"/>"#))
+            .unwrap_or_default();
+
+        format!(
+            r#"<memberdef kind="{kind}" id="class_a_1{name}" prot="public" static="no">
+                <name>{name}</name>
+                {location}
+            </memberdef>"#
+        )
+    }
+
+    fn sorted_names(sort: &SortStrategy, members: &[String]) -> Vec<String> {
+        let members: Vec<_> = members.iter().map(String::as_str).collect();
+        let mut member_defs = parse_member_defs(&members);
+        member_defs.sort_by(|a, b| cmp_member_defs(sort, a, b));
+        member_defs
+            .into_iter()
+            .map(|member_def| member_def.name)
+            .collect()
+    }
+
+    fn reimplement(refid: &str, name: &str) -> e::ReimplementType {
+        e::ReimplementType {
+            refid: refid.to_string(),
+            content: name.to_string(),
+        }
+    }
+
+    /// Creates an xml loader for a new directory, within the temporary directory, containing the files
+    fn xml_loader(name: &str, files: &[(&str, &str)]) -> (PathBuf, XmlLoader) {
+        let root = std::env::temp_dir().join(format!("docleaf-{name}-{}", std::process::id()));
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        let cache = crate::cache::TrackedCache::new(&crate::cache::FileCache::new());
+        (root.clone(), XmlLoader::new(root, cache))
+    }
+
+    fn body_location(file: &str, start: i32, end: i32) -> e::LocationType {
+        e::LocationType {
+            file: file.to_string(),
+            line: Some(start),
+            column: None,
+            declfile: None,
+            declline: None,
+            declcolumn: None,
+            bodyfile: Some(file.to_string()),
+            bodystart: Some(start),
+            bodyend: Some(end),
+        }
+    }
+
+    /// A file compound with a program listing of three lines where the second line declares 'f'
+    fn file_compound(refid: &str, file: &str) -> String {
+        format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="{refid}" kind="file" language="C++">
+                    <compoundname>a.h</compoundname>
+                    <programlisting>
+                        <codeline lineno="1"><highlight class="normal">#pragma<sp/>once</highlight></codeline>
+                        <codeline lineno="2" refid="{refid}_1f" refkind="member"><highlight class="normal">void<sp/>f();</highlight></codeline>
+                        <codeline lineno="3"><highlight class="normal">int<sp/>g();</highlight></codeline>
+                    </programlisting>
+                    <location file="{file}"/>
+                </compounddef>
+            </doxygen>"#
+        )
+    }
+
+    /// Lists the sections, by id and title, and the text of the paragraphs, indented by the depth of the section
+    fn outline(nodes: &[Node], depth: usize) -> Vec<String> {
+        let indent = "  ".repeat(depth);
+
+        nodes
+            .iter()
+            .flat_map(|node| match node {
+                Node::Section {
+                    target,
+                    title,
+                    nodes,
+                } => {
+                    let mut lines = vec![format!("{indent}{}: {}", target.ids, text(title))];
+                    lines.append(&mut outline(nodes, depth + 1));
+                    lines
+                }
+                Node::Paragraph(nodes) => vec![format!("{indent}{}", text(nodes))],
+                node => panic!("Unexpected node: {node:?}"),
+            })
+            .collect()
+    }
+
+    /// An example compound whose listing refers to the members
+    fn example_compound(refid: &str, member_refids: &[&str]) -> String {
+        let refs: String = member_refids
+            .iter()
+            .map(|refid| format!(r#"<ref refid="{refid}" kindref="member">f</ref>();"#))
+            .collect();
+
+        format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="{refid}" kind="example">
+                    <compoundname>{refid}.cpp</compoundname>
+                    <detaileddescription>
+                        <para><programlisting filename=".cpp">
+                            <codeline><highlight class="normal">{refs}</highlight></codeline>
+                        </programlisting></para>
+                    </detaileddescription>
+                </compounddef>
+            </doxygen>"#
+        )
+    }
+
+    /// A directory or file compound with a brief description
+    fn listed_compound(refid: &str, name: &str, kind: &str, inner: &str) -> String {
+        format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="{refid}" kind="{kind}">
+                    <compoundname>{name}</compoundname>
+                    {inner}
+                    <briefdescription><para>About {name}</para></briefdescription>
+                    <location file="{name}"/>
+                </compounddef>
+            </doxygen>"#
+        )
+    }
+
+    /// Lists the text of the paragraphs in the list items indented by the depth of the list
+    fn list_outline(items: &[Node], depth: usize) -> Vec<String> {
+        let indent = "  ".repeat(depth);
+
+        items
+            .iter()
+            .flat_map(|item| match item {
+                Node::ListItem(nodes) => nodes.iter().flat_map(|node| match node {
+                    Node::Paragraph(nodes) => vec![format!("{indent}{}", text(nodes))],
+                    Node::BulletList(items) => list_outline(items, depth + 1),
+                    node => panic!("Unexpected node: {node:?}"),
+                }),
+                node => panic!("Unexpected node: {node:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn render_variable_list_pairs_terms_with_items() {
+        let paras = parse_paras(
+            r#"<para><variablelist>
+                <varlistentry><term>First</term></varlistentry>
+                <listitem><para>One</para></listitem>
+                <varlistentry><term>Second</term></varlistentry>
+                <listitem><para>Two</para><para>Three</para></listitem>
+            </variablelist></para>"#,
+        );
+
+        let items = match render_doc_para_types(&Context::default(), &paras).as_slice() {
+            [Node::DefinitionList(items)] => items.clone(),
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        };
+
+        let items: Vec<_> = items
+            .iter()
+            .map(|item| match item {
+                Node::DefinitionListItem(nodes) => match nodes.as_slice() {
+                    [Node::Term(term), Node::Definition(definition)] => {
+                        (text(term), text(definition))
+                    }
+                    nodes => panic!("Unexpected nodes: {nodes:?}"),
+                },
+                node => panic!("Unexpected node: {node:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            items,
+            [
+                ("First".to_string(), "One".to_string()),
+                ("Second".to_string(), "TwoThree".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn render_block_quote_outside_of_paragraph() {
+        let paras =
+            parse_paras("<para>Before<blockquote><para>Quoted</para></blockquote>After</para>");
+
+        match render_doc_para_types(&Context::default(), &paras).as_slice() {
+            [Node::Paragraph(before), Node::BlockQuote(quote), Node::Paragraph(after)] => {
+                assert_eq!(text(before), "Before");
+                assert_eq!(text(quote), "Quoted");
+                assert_eq!(text(after), "After");
+            }
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn render_details_with_summary() {
+        let paras = parse_paras(
+            "<para><details><summary>More</summary><para>Hidden</para></details></para>",
+        );
+
+        match render_doc_para_types(&Context::default(), &paras).as_slice() {
+            [Node::Details(nodes)] => match nodes.as_slice() {
+                [Node::DetailsSummary(summary), rest @ ..] => {
+                    assert_eq!(text(summary), "More");
+                    assert_eq!(text(rest), "Hidden");
+                }
+                nodes => panic!("Unexpected nodes: {nodes:?}"),
+            },
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn render_details_without_summary() {
+        let paras = parse_paras("<para><details><para>Hidden</para></details></para>");
+
+        match render_doc_para_types(&Context::default(), &paras).as_slice() {
+            [Node::Details(nodes)] => assert_eq!(text(nodes), "Hidden"),
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn listing_extension_from_file_name() {
+        assert_eq!(listing_extension(Some(".py")), Some("py"));
+        assert_eq!(listing_extension(Some("foo.py")), Some("py"));
+        assert_eq!(listing_extension(Some("src/foo.tar.gz")), Some("gz"));
+        assert_eq!(listing_extension(Some("Makefile")), None);
+        assert_eq!(listing_extension(None), None);
+    }
+
+    #[test]
+    fn listing_language_prefers_the_extension() {
+        let ctx = Context {
+            domain: Some(Domain::CPlusPlus),
+            ..Context::default()
+        };
+
+        assert_eq!(
+            listing_language(&ctx, Some(".py")),
+            Some("python".to_string())
+        );
+        assert_eq!(
+            listing_language(&ctx, Some("foo.py")),
+            Some("python".to_string())
+        );
+        assert_eq!(listing_language(&ctx, None), Some("cpp".to_string()));
+    }
+
+    #[test]
+    fn listing_language_uses_the_domain_by_extension() {
+        let ctx = Context {
+            extension_domain_lookup: HashMap::from([("h".to_string(), Domain::C)]),
+            language: Some("cpp"),
+            ..Context::default()
+        };
+
+        assert_eq!(listing_language(&ctx, Some(".h")), Some("c".to_string()));
+        assert_eq!(
+            listing_language(&ctx, Some("unknown.xyz")),
+            Some("cpp".to_string())
+        );
+    }
+
+    #[test]
+    fn cmp_member_defs_xml_keeps_order() {
+        let members = [
+            member("variable", "b", Some(("a.h", 2))),
+            member("function", "a", Some(("a.h", 1))),
+        ];
+
+        assert_eq!(sorted_names(&SortStrategy::Xml, &members), ["b", "a"]);
+    }
+
+    #[test]
+    fn cmp_member_defs_declaration_compares_lines_within_files() {
+        let members = [
+            member("function", "c", None),
+            member("function", "b", Some(("b.h", 1))),
+            member("function", "a2", Some(("a.h", 20))),
+            member("function", "a1", Some(("a.h", 3))),
+        ];
+
+        assert_eq!(
+            sorted_names(&SortStrategy::Declaration, &members),
+            ["a1", "a2", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn cmp_member_defs_kind_then_declaration() {
+        let members = [
+            member("variable", "v", Some(("a.h", 1))),
+            member("function", "f2", Some(("a.h", 4))),
+            member("function", "f1", Some(("a.h", 2))),
+        ];
+
+        assert_eq!(
+            sorted_names(&SortStrategy::Kind, &members),
+            ["f1", "f2", "v"]
+        );
+    }
+
+    #[test]
+    fn cmp_member_defs_alphabetical_then_declaration() {
+        let members = [
+            member("function", "b", Some(("a.h", 1))),
+            member("function", "a", Some(("a.h", 9))),
+            member("function", "a", Some(("a.h", 5))),
+        ];
+
+        let members: Vec<_> = members.iter().map(String::as_str).collect();
+        let mut member_defs = parse_member_defs(&members);
+        member_defs.sort_by(|a, b| cmp_member_defs(&SortStrategy::Alphabetical, a, b));
+
+        let sorted: Vec<_> = member_defs
+            .iter()
+            .map(|member_def| {
+                let line = member_def
+                    .location
+                    .as_ref()
+                    .and_then(|location| location.line);
+                (member_def.name.as_str(), line)
+            })
+            .collect();
+
+        assert_eq!(sorted, [("a", Some(5)), ("a", Some(9)), ("b", Some(1))]);
+    }
+
+    #[test]
+    fn cmp_section_defs_xml_and_declaration() {
+        let xml = format!(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="class_a" kind="class" language="C++" prot="public">
+                    <compoundname>A</compoundname>
+                    <sectiondef kind="private-attrib">{}</sectiondef>
+                    <sectiondef kind="public-func">{}</sectiondef>
+                </compounddef>
+            </doxygen>"#,
+            member("variable", "a", Some(("a.h", 1))),
+            member("function", "b", Some(("a.h", 2)))
+        );
+
+        let mut section_defs = e::parse(&xml).unwrap().compounddef.unwrap().sectiondef;
+        let titles = |section_defs: &[e::SectiondefType]| -> Vec<String> {
+            section_defs
+                .iter()
+                .map(|section_def| section_title(&section_def.kind))
+                .collect()
+        };
+
+        section_defs.sort_by(|a, b| cmp_section_defs(&SortStrategy::Xml, a, b));
+        assert_eq!(
+            titles(&section_defs),
+            ["Public Functions", "Private Attributes"]
+        );
+
+        section_defs.sort_by(|a, b| cmp_section_defs(&SortStrategy::Declaration, a, b));
+        assert_eq!(
+            titles(&section_defs),
+            ["Private Attributes", "Public Functions"]
+        );
+    }
+
+    #[test]
+    fn compound_refid_from_member_refid() {
+        assert_eq!(
+            compound_refid("class_nutshell_1ae42034231cf912d095d57cbeed6cda79"),
+            "class_nutshell"
+        );
+        assert_eq!(
+            compound_refid("namespace_1_1inner_1a57fc2641156575ef8d655b6c90a1a17d"),
+            "namespace_1_1inner"
+        );
+        assert_eq!(compound_refid("class_nutshell"), "class_nutshell");
+    }
+
+    #[test]
+    fn render_member_ref_with_qualifiers() {
+        let member = e::MemberRefType {
+            refid: "class_base_1a".to_string(),
+            prot: e::DoxProtectionKind::Protected,
+            virt: e::DoxVirtualKind::PureVirtual,
+            ambiguityscope: Some("Left::".to_string()),
+            scope: "Base".to_string(),
+            name: "run".to_string(),
+        };
+
+        let Node::ListItem(item) = render_member_ref(&member) else {
+            panic!("Expected a list item");
+        };
+        let [Node::Paragraph(nodes)] = item.as_slice() else {
+            panic!("Unexpected nodes: {item:?}");
+        };
+        let [Node::OptionalReference { refid, children }, Node::Text(qualifiers)] =
+            nodes.as_slice()
+        else {
+            panic!("Unexpected nodes: {nodes:?}");
+        };
+
+        assert_eq!(refid, "class_base_1a");
+        assert_eq!(text(children), "Left::run");
+        assert_eq!(qualifiers, " (protected, pure virtual)");
+    }
+
+    #[test]
+    fn render_member_ref_without_qualifiers() {
+        let member = e::MemberRefType {
+            refid: "class_base_1a".to_string(),
+            prot: e::DoxProtectionKind::Public,
+            virt: e::DoxVirtualKind::NonVirtual,
+            ambiguityscope: None,
+            scope: "Base".to_string(),
+            name: "run".to_string(),
+        };
+
+        let Node::ListItem(item) = render_member_ref(&member) else {
+            panic!("Expected a list item");
+        };
+        let [Node::Paragraph(nodes)] = item.as_slice() else {
+            panic!("Unexpected nodes: {item:?}");
+        };
+
+        let [Node::OptionalReference { children, .. }] = nodes.as_slice() else {
+            panic!("Unexpected nodes: {nodes:?}");
+        };
+
+        assert_eq!(text(children), "run");
+    }
+
+    #[test]
+    fn reimplement_name_qualified_by_compound() {
+        let index = index::parse(
+            r#"<doxygenindex version="1.9.7">
+                <compound refid="class_base" kind="class"><name>ns::Base</name></compound>
+            </doxygenindex>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            reimplement_name(&reimplement("class_base_1a1", "run"), Some(&index)),
+            "ns::Base::run"
+        );
+        assert_eq!(
+            reimplement_name(&reimplement("class_other_1a1", "run"), Some(&index)),
+            "run"
+        );
+        assert_eq!(
+            reimplement_name(&reimplement("class_base_1a1", "run"), None),
+            "run"
+        );
+    }
+
+    #[test]
+    fn render_member_link_list_with_optional_links() {
+        let members = [("class_base_1a1", "Base::run".to_string())];
+
+        match render_member_link_list("title-class", "Reimplements", members.into_iter()).as_slice()
+        {
+            [Node::Rubric { classes, nodes }, Node::BulletList(items)] => {
+                assert_eq!(classes, &["title-class"]);
+                assert_eq!(text(nodes), "Reimplements");
+
+                let [Node::ListItem(item)] = items.as_slice() else {
+                    panic!("Unexpected nodes: {items:?}");
+                };
+                let [Node::Paragraph(nodes)] = item.as_slice() else {
+                    panic!("Unexpected nodes: {item:?}");
+                };
+                let [Node::OptionalReference { refid, children }] = nodes.as_slice() else {
+                    panic!("Unexpected nodes: {nodes:?}");
+                };
+
+                assert_eq!(refid, "class_base_1a1");
+                assert_eq!(text(children), "Base::run");
+            }
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn render_inbody_description_settings() {
+        let description = e::DescriptionType {
+            title: None,
+            para: parse_paras("<para>Note</para>"),
+            internal: Vec::new(),
+            sect1: Vec::new(),
+        };
+        let ctx = |inbody_descriptions| Context {
+            inbody_descriptions,
+            ..Context::default()
+        };
+
+        assert!(
+            render_inbody_description(&ctx(InbodyDescriptions::Hidden), &description).is_empty()
+        );
+
+        match render_inbody_description(&ctx(InbodyDescriptions::Append), &description).as_slice() {
+            [Node::Paragraph(nodes)] => assert_eq!(text(nodes), "Note"),
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+
+        match render_inbody_description(&ctx(InbodyDescriptions::Rubric), &description).as_slice() {
+            [Node::Rubric { classes, nodes }, Node::Paragraph(paragraph)] => {
+                assert_eq!(classes, &["docleaf-inbody-description-title"]);
+                assert_eq!(text(nodes), "Implementation notes");
+                assert_eq!(text(paragraph), "Note");
+            }
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn render_inbody_description_without_content() {
+        let description = e::DescriptionType {
+            title: None,
+            para: Vec::new(),
+            internal: Vec::new(),
+            sect1: Vec::new(),
+        };
+        let ctx = Context {
+            inbody_descriptions: InbodyDescriptions::Rubric,
+            ..Context::default()
+        };
+
+        assert!(render_inbody_description(&ctx, &description).is_empty());
+    }
+
+    #[test]
+    fn render_location_for_definition_and_declaration() {
+        let location = e::LocationType {
+            file: "src/impl.cpp".to_string(),
+            line: Some(10),
+            column: None,
+            declfile: Some("include/api.h".to_string()),
+            declline: Some(3),
+            declcolumn: None,
+            bodyfile: Some("src/impl.cpp".to_string()),
+            bodystart: Some(12),
+            bodyend: Some(20),
+        };
+        let ctx = |source_location| Context {
+            project_root: PathBuf::from("/project"),
+            source_location,
+            ..Context::default()
+        };
+        let render = |source_location| {
+            render_location(&ctx(source_location), Some(&location))
+                .map(|location| (location.path, location.line))
+        };
+
+        assert_eq!(
+            render(SourceLocation::Definition),
+            Some(("/project/src/impl.cpp".to_string(), 10))
+        );
+        assert_eq!(
+            render(SourceLocation::Declaration),
+            Some(("/project/include/api.h".to_string(), 3))
+        );
+    }
+
+    #[test]
+    fn render_location_falls_back_to_body() {
+        let location = e::LocationType {
+            file: "include/api.h".to_string(),
+            line: None,
+            column: None,
+            declfile: None,
+            declline: None,
+            declcolumn: None,
+            bodyfile: Some("src/impl.cpp".to_string()),
+            bodystart: Some(12),
+            bodyend: Some(20),
+        };
+
+        let location = render_location(&Context::default(), Some(&location)).unwrap();
+        assert_eq!(
+            (location.path.as_str(), location.line),
+            ("src/impl.cpp", 12)
+        );
+    }
+
+    #[test]
+    fn render_location_without_lines() {
+        let location = e::LocationType {
+            file: "include/api.h".to_string(),
+            line: None,
+            column: None,
+            declfile: None,
+            declline: None,
+            declcolumn: None,
+            bodyfile: None,
+            bodystart: Some(-1),
+            bodyend: Some(-1),
+        };
+
+        assert!(render_location(&Context::default(), Some(&location)).is_none());
+        assert!(render_location(&Context::default(), None).is_none());
+    }
+
+    #[test]
+    fn read_source_lines_from_project_root() {
+        let (root, mut xml_loader) =
+            xml_loader("read-source", &[("src/a.cpp", "one\ntwo\nthree\nfour\n")]);
+        let ctx = Context {
+            project_root: root,
+            ..Context::default()
+        };
+
+        assert_eq!(
+            read_source_lines(&ctx, "src/a.cpp", 2, 3, &mut xml_loader),
+            Some("two\nthree".to_string())
+        );
+        assert_eq!(
+            read_source_lines(&ctx, "src/missing.cpp", 2, 3, &mut xml_loader),
+            None
+        );
+    }
+
+    #[test]
+    fn render_implementation_source_from_file() {
+        let (root, mut xml_loader) = xml_loader(
+            "implementation-file",
+            &[("src/a.py", "def f():\n    pass\n")],
+        );
+        let ctx = Context {
+            project_root: root,
+            implementation_source: ImplementationSource::File,
+            listing_line_numbers: true,
+            ..Context::default()
+        };
+        let location = body_location("src/a.py", 1, 2);
+
+        match render_implementation_source(&ctx, "a_8py_1f", Some(&location), &mut xml_loader)
+            .as_slice()
+        {
+            [Node::Rubric { nodes, .. }, Node::CodeBlock {
+                language,
+                line_number_start,
+                highlighted_lines,
+                nodes: code,
+            }] => {
+                assert_eq!(text(nodes), "Implementation");
+                assert_eq!(language.as_deref(), Some("python"));
+                assert_eq!(*line_number_start, Some(1));
+                assert!(highlighted_lines.is_empty());
+                assert_eq!(text(code), "def f():\n    pass");
+            }
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn render_implementation_source_without_body() {
+        let (_, mut xml_loader) = xml_loader("implementation-none", &[]);
+        let ctx = Context {
+            implementation_source: ImplementationSource::File,
+            ..Context::default()
+        };
+        let location = body_location("src/a.h", -1, -1);
+
+        assert!(
+            render_implementation_source(&ctx, "a_8h_1f", Some(&location), &mut xml_loader)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn render_listing_lines_from_file_compound() {
+        let (_, mut xml_loader) = xml_loader(
+            "listing-lines",
+            &[
+                (
+                    "index.xml",
+                    r#"<doxygenindex version="1.9.7">
+                        <compound refid="other_2a_8h" kind="file"><name>a.h</name></compound>
+                        <compound refid="a_8h" kind="file"><name>a.h</name></compound>
+                    </doxygenindex>"#,
+                ),
+                (
+                    "other_2a_8h.xml",
+                    &file_compound("other_2a_8h", "other/a.h"),
+                ),
+                ("a_8h.xml", &file_compound("a_8h", "src/a.h")),
+            ],
+        );
+
+        let (nodes, highlighted_lines) = render_listing_lines(
+            &Context::default(),
+            "a_8h_1f",
+            "src/a.h",
+            2,
+            3,
+            &mut xml_loader,
+        );
+
+        assert_eq!(text(&nodes), "void f();\nint g();");
+        assert_eq!(highlighted_lines, [1]);
+    }
+
+    #[test]
+    fn render_page_with_sections_and_sub_pages() {
+        let (_, mut xml_loader) = xml_loader(
+            "page",
+            &[(
+                "page_b.xml",
+                r#"<doxygen version="1.9.7">
+                    <compounddef id="page_b" kind="page">
+                        <compoundname>page_b</compoundname>
+                        <detaileddescription><para>Details</para></detaileddescription>
+                    </compounddef>
+                </doxygen>"#,
+            )],
+        );
+        let root = e::parse(
+            r#"<doxygen version="1.9.7">
+                <compounddef id="page_a" kind="page">
+                    <compoundname>page_a</compoundname>
+                    <title>Guide</title>
+                    <innerpage refid="page_b">page_b</innerpage>
+                    <detaileddescription>
+                        <para>Intro</para>
+                        <sect1 id="page_a_1setup">
+                            <title>Setup</title>
+                            <para>Install</para>
+                            <sect2 id="page_a_1linux">
+                                <title>Linux</title>
+                                <para>Use apt</para>
+                            </sect2>
+                        </sect1>
+                    </detaileddescription>
+                </compounddef>
+            </doxygen>"#,
+        )
+        .unwrap();
+
+        let nodes = render_page(&Context::default(), &root, false, &mut xml_loader).unwrap();
+
+        assert_eq!(
+            outline(&nodes, 0),
+            [
+                "page_a: Guide",
+                "  Intro",
+                "  page_a_1setup: Setup",
+                "    Install",
+                "    page_a_1linux: Linux",
+                "      Use apt",
+                "  page_b: page_b",
+                "    Details",
+            ]
+        );
+
+        let nodes = render_page(&Context::default(), &root, true, &mut xml_loader).unwrap();
+        assert_eq!(outline(&nodes, 0)[0], "Intro");
+    }
+
+    #[test]
+    fn example_refids_without_duplicates() {
+        let root = e::parse(&example_compound(
+            "first",
+            &["class_a_1f", "class_a_1g", "class_a_1f"],
+        ))
+        .unwrap();
+
+        assert_eq!(
+            example_refids(&root.compounddef.unwrap()),
+            BTreeSet::from(["class_a_1f", "class_a_1g"])
+        );
+    }
+
+    #[test]
+    fn render_member_examples_from_every_example() {
+        let (_, mut xml_loader) = xml_loader(
+            "member-examples",
+            &[
+                (
+                    "index.xml",
+                    r#"<doxygenindex version="1.9.7">
+                        <compound refid="class_a" kind="class"><name>A</name></compound>
+                        <compound refid="first" kind="example"><name>first.cpp</name></compound>
+                        <compound refid="second" kind="example"><name>second.cpp</name></compound>
+                    </doxygenindex>"#,
+                ),
+                ("first.xml", &example_compound("first", &["class_a_1f"])),
+                (
+                    "second.xml",
+                    &example_compound("second", &["class_a_1g", "class_a_1f"]),
+                ),
+            ],
+        );
+
+        match render_member_examples("class_a_1f", &mut xml_loader).as_slice() {
+            [Node::Rubric { nodes, .. }, Node::BulletList(items)] => {
+                assert_eq!(text(nodes), "Examples");
+
+                let examples: Vec<_> = items
+                    .iter()
+                    .map(|item| match item {
+                        Node::ListItem(nodes) => match nodes.as_slice() {
+                            [Node::Paragraph(nodes)] => match nodes.as_slice() {
+                                [Node::OptionalReference { refid, children }] => {
+                                    (refid.clone(), text(children))
+                                }
+                                nodes => panic!("Unexpected nodes: {nodes:?}"),
+                            },
+                            nodes => panic!("Unexpected nodes: {nodes:?}"),
+                        },
+                        node => panic!("Unexpected node: {node:?}"),
+                    })
+                    .collect();
+
+                assert_eq!(
+                    examples,
+                    [
+                        ("first".to_string(), "first.cpp".to_string()),
+                        ("second".to_string(), "second.cpp".to_string())
+                    ]
+                );
+            }
+            nodes => panic!("Unexpected nodes: {nodes:?}"),
+        }
+
+        assert!(render_member_examples("class_a_1h", &mut xml_loader).is_empty());
+    }
+
+    #[test]
+    fn render_dir_contents_to_depth() {
+        let (_, mut xml_loader) = xml_loader(
+            "dir-contents",
+            &[
+                (
+                    "dir_core.xml",
+                    &listed_compound(
+                        "dir_core",
+                        "src/core",
+                        "dir",
+                        r#"<innerdir refid="dir_deep">src/core/deep</innerdir>
+                        <innerfile refid="core_8c">core.c</innerfile>"#,
+                    ),
+                ),
+                (
+                    "dir_deep.xml",
+                    &listed_compound("dir_deep", "src/core/deep", "dir", ""),
+                ),
+                (
+                    "core_8c.xml",
+                    &listed_compound("core_8c", "core.c", "file", ""),
+                ),
+                (
+                    "main_8c.xml",
+                    &listed_compound("main_8c", "main.c", "file", ""),
+                ),
+            ],
+        );
+        let root = e::parse(&listed_compound(
+            "dir_src",
+            "src",
+            "dir",
+            r#"<innerdir refid="dir_core">src/core</innerdir>
+            <innerfile refid="main_8c">main.c</innerfile>
+            <innerfile refid="missing_8c">missing.c</innerfile>"#,
+        ))
+        .unwrap();
+        let compound_def = root.compounddef.unwrap();
+        let ctx = Context::default();
+
+        assert_eq!(
+            list_outline(
+                &render_dir_contents(&ctx, &compound_def, None, &mut xml_loader),
+                0
+            ),
+            [
+                "core/",
+                "About src/core",
+                "  deep/",
+                "  About src/core/deep",
+                "  core.c",
+                "  About core.c",
+                "main.c",
+                "About main.c",
+            ]
+        );
+        assert_eq!(
+            list_outline(
+                &render_dir_contents(&ctx, &compound_def, Some(1), &mut xml_loader),
+                0
+            ),
+            ["core/", "About src/core", "main.c", "About main.c"]
+        );
+    }
+}
@@ -6,13 +6,138 @@ use crate::doxygen::compound::generated as e;
 use crate::Domain;
 
 pub fn render_compound_def(domain: &Domain, compound_def: &e::CompounddefType) -> String {
-    match domain {
+    let name = match domain {
         Domain::CPlusPlus => compound_def.compoundname.to_string(),
         // For C, for Sphinx, we want to express it with a '.' instead of '::'
         Domain::C => compound_def.compoundname.replace("::", "."),
+    };
+
+    let declaration = match (domain, &compound_def.kind) {
+        (Domain::CPlusPlus, e::DoxCompoundKind::Class | e::DoxCompoundKind::Struct)
+            if !compound_def.basecompoundref.is_empty() =>
+        {
+            let bases = compound_def
+                .basecompoundref
+                .iter()
+                .map(render_base_compound_ref)
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{name} : {bases}")
+        }
+        (Domain::CPlusPlus, e::DoxCompoundKind::Concept) => {
+            match compound_def
+                .initializer
+                .as_ref()
+                .map(render_concept_constraint)
+            {
+                Some(constraint) if !constraint.is_empty() => format!("{name} = {constraint}"),
+                _ => name,
+            }
+        }
+        _ => name,
+    };
+
+    // Class templates can be constrained with a requires-clause after the template parameter list
+    let declaration = match (domain, compound_def.requiresclause.as_ref()) {
+        (Domain::CPlusPlus, Some(requiresclause)) => {
+            let requiresclause = collapse_lines(&render_linked_text_type(requiresclause));
+            format!("requires {} {declaration}", requiresclause.trim())
+        }
+        _ => declaration,
+    };
+
+    with_template_prefix(domain, compound_def.templateparamlist.as_ref(), declaration)
+}
+
+/// Extracts the constraint expression from the initializer of a concept. Doxygen may provide either the whole
+/// definition, eg. 'template<typename T> concept Number = std::is_arithmetic_v<T>', or just the
+/// part after the name so we remove everything up to and including the '='
+fn render_concept_constraint(initializer: &e::LinkedTextType) -> String {
+    let initializer = collapse_lines(&render_linked_text_type(initializer));
+
+    let constraint = match find_word(&initializer, "concept") {
+        Some(position) => {
+            let rest = &initializer[position..];
+            rest.find('=').map(|index| &rest[index..]).unwrap_or("")
+        }
+        None => initializer.as_str(),
+    };
+
+    constraint.trim().trim_start_matches('=').trim().to_string()
+}
+
+/// Renders an entry for the base clause of a class declaration, eg. 'public virtual Base'
+fn render_base_compound_ref(compound_ref: &e::CompoundRefType) -> String {
+    let protection = match compound_ref.prot {
+        e::DoxProtectionKind::Public => Some("public"),
+        e::DoxProtectionKind::Protected => Some("protected"),
+        e::DoxProtectionKind::Private => Some("private"),
+        // Package protection is only relevant to Java so we have no C++ syntax for it
+        e::DoxProtectionKind::Package => None,
+    };
+
+    let virtual_ = match compound_ref.virt {
+        e::DoxVirtualKind::NonVirtual => None,
+        e::DoxVirtualKind::Virtual | e::DoxVirtualKind::PureVirtual => Some("virtual"),
+    };
+
+    let name = html_escape::decode_html_entities(&compound_ref.content);
+
+    [protection, virtual_, Some(name.as_ref())]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Prefixes the declaration with the template parameter list, if there is one, so that the Sphinx C++ domain
+/// registers the entity as a template. C has no templates so we leave the declaration untouched for the C domain
+fn with_template_prefix(
+    domain: &Domain,
+    templateparamlist: Option<&e::TemplateparamlistType>,
+    declaration: String,
+) -> String {
+    match (domain, templateparamlist) {
+        (Domain::CPlusPlus, Some(templateparamlist)) => {
+            format!(
+                "{} {declaration}",
+                render_template_param_list(templateparamlist)
+            )
+        }
+        _ => declaration,
     }
 }
 
+/// Renders the template parameter list in C++ syntax, eg. 'template<typename T, int N = 5>'
+pub fn render_template_param_list(templateparamlist: &e::TemplateparamlistType) -> String {
+    let params = templateparamlist
+        .param
+        .iter()
+        .map(render_template_param)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("template<{params}>")
+}
+
+fn render_template_param(param: &e::ParamType) -> String {
+    [
+        // For type parameters the type contains the keyword and often the name too, eg. 'typename T'
+        param.type_.as_ref().map(render_linked_text_type),
+        param.declname.clone(),
+        param.array.clone(),
+        param
+            .defval
+            .as_ref()
+            .map(render_linked_text_type)
+            .map(|defval| format!("= {defval}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
 pub fn render_compound_kind(kind: &e::DoxCompoundKind) -> &'static str {
     match kind {
         e::DoxCompoundKind::Class => "class",
@@ -73,40 +198,57 @@ fn if_yes(dox_bool: &e::DoxBool, str: &str) -> Option<String> {
     }
 }
 
+/// Renders the declaration for the member. Initializers are never truncated as the declaration is parsed by the
+/// Sphinx domains which can't handle partial expressions
 pub fn render_member_def(domain: &Domain, member_def: &e::MemberdefType) -> String {
+    with_template_prefix(
+        domain,
+        member_def.templateparamlist.as_ref(),
+        render_member_def_declaration(domain, member_def),
+    )
+}
+
+fn render_member_def_declaration(domain: &Domain, member_def: &e::MemberdefType) -> String {
     match member_def.kind {
-        e::DoxMemberKind::Function => [
-            if_yes(&member_def.static_, "static "),
-            if_yes(
-                member_def.inline.as_ref().unwrap_or(&e::DoxBool::No),
-                "inline ",
-            ),
-            if_yes(
-                member_def.const_.as_ref().unwrap_or(&e::DoxBool::No),
-                "const ",
-            ),
-            member_def
-                .type_
-                .as_ref()
-                .map(render_linked_text_type)
-                .map(|str| with_trailing(str, " ")),
+        e::DoxMemberKind::Function => {
+            let type_ = member_def.type_.as_ref().map(render_linked_text_type);
+
             match domain {
-                // If we're in the C++ domain then try to use the qualified name if possible so that it registers
-                // as a class member when it is a class member
-                Domain::CPlusPlus => Some(
+                Domain::CPlusPlus => [
+                    render_function_specifiers(member_def, type_.as_deref().unwrap_or_default()),
+                    type_.map(|str| with_trailing(str, " ")),
+                    // Try to use the qualified name if possible so that it registers as a class member when it is
+                    // a class member
+                    Some(
+                        member_def
+                            .qualifiedname
+                            .as_ref()
+                            .unwrap_or(&member_def.name)
+                            .clone(),
+                    ),
                     member_def
-                        .qualifiedname
-                        .as_ref()
-                        .unwrap_or(&member_def.name)
-                        .clone(),
-                ),
-                Domain::C => Some(member_def.name.clone()),
-            },
-            member_def.argsstring.clone(),
-        ]
-        .into_iter()
-        .flatten()
-        .collect(),
+                        .argsstring
+                        .as_deref()
+                        .map(|argsstring| render_function_args(member_def, argsstring)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+                Domain::C => [
+                    if_yes(&member_def.static_, "static "),
+                    if_yes(
+                        member_def.inline.as_ref().unwrap_or(&e::DoxBool::No),
+                        "inline ",
+                    ),
+                    type_.map(|str| with_trailing(str, " ")),
+                    Some(member_def.name.clone()),
+                    member_def.argsstring.clone(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            }
+        }
         e::DoxMemberKind::Define => {
             if member_def.param.is_empty() {
                 member_def.name.clone()
@@ -172,6 +314,11 @@ pub fn render_member_def(domain: &Domain, member_def: &e::MemberdefType) -> Stri
                         None
                     }
                 }),
+                member_def.bitfield.as_deref().map(render_bitfield),
+                member_def
+                    .initializer
+                    .as_ref()
+                    .map(|initializer| render_initializer(initializer, None)),
             ]
             .into_iter()
             .flatten()
@@ -185,6 +332,252 @@ pub fn render_member_def(domain: &Domain, member_def: &e::MemberdefType) -> Stri
     }
 }
 
+/// Renders the declaration specifiers which come before the return type, eg. 'static constexpr '. Doxygen sometimes
+/// leaves specifiers in the type so we skip any that are already there
+fn render_function_specifiers(member_def: &e::MemberdefType, type_: &str) -> Option<String> {
+    let is_yes = |dox_bool: &Option<e::DoxBool>| dox_bool.as_ref() == Some(&e::DoxBool::Yes);
+
+    let specifiers = [
+        (member_def.static_ == e::DoxBool::Yes, "static"),
+        (is_yes(&member_def.inline), "inline"),
+        (is_yes(&member_def.constexpr), "constexpr"),
+        (
+            matches!(
+                member_def.virt,
+                Some(e::DoxVirtualKind::Virtual | e::DoxVirtualKind::PureVirtual)
+            ),
+            "virtual",
+        ),
+        (is_yes(&member_def.explicit), "explicit"),
+    ];
+
+    let specifiers: String = specifiers
+        .into_iter()
+        .filter(|(enabled, specifier)| {
+            *enabled && !type_.split_whitespace().any(|word| word == *specifier)
+        })
+        .map(|(_, specifier)| with_trailing(specifier.to_string(), " "))
+        .collect();
+
+    option_from_str(&specifiers).map(str::to_string)
+}
+
+/// Renders the parameter list followed by the qualifiers of the function. Doxygen includes most qualifiers in the
+/// argsstring but not consistently across versions so we add any of the qualifiers indicated by the attributes that
+/// are missing, in the order that C++ requires them, eg. '(int a) const & noexcept override = 0'
+fn render_function_args(member_def: &e::MemberdefType, argsstring: &str) -> String {
+    let argsstring = html_escape::decode_html_entities(argsstring);
+
+    let Some(params_end) = find_params_end(&argsstring) else {
+        return argsstring.to_string();
+    };
+
+    let (params, tail) = argsstring.split_at(params_end);
+    let (mut qualifiers, rest) = split_leading_qualifiers(tail);
+    let mut rest = rest.trim().to_string();
+
+    let is_yes = |dox_bool: &Option<e::DoxBool>| dox_bool.as_ref() == Some(&e::DoxBool::Yes);
+    let has_qualifier = |qualifiers: &[String], prefix: &str| {
+        qualifiers
+            .iter()
+            .any(|qualifier| qualifier.starts_with(prefix))
+    };
+
+    if is_yes(&member_def.const_) && !has_qualifier(&qualifiers, "const") {
+        qualifiers.insert(0, "const".to_string());
+    }
+
+    if is_yes(&member_def.volatile) && !has_qualifier(&qualifiers, "volatile") {
+        let position = usize::from(has_qualifier(&qualifiers, "const"));
+        qualifiers.insert(position, "volatile".to_string());
+    }
+
+    if let Some(ref refqual) = member_def.refqual {
+        if !has_qualifier(&qualifiers, "&") {
+            let position = qualifiers
+                .iter()
+                .take_while(|qualifier| *qualifier == "const" || *qualifier == "volatile")
+                .count();
+            let refqual = match refqual {
+                e::DoxRefQualifierKind::Lvalue => "&",
+                e::DoxRefQualifierKind::Rvalue => "&&",
+            };
+            qualifiers.insert(position, refqual.to_string());
+        }
+    }
+
+    let has_exception_spec =
+        has_qualifier(&qualifiers, "noexcept") || has_qualifier(&qualifiers, "throw");
+
+    if !has_exception_spec {
+        if is_yes(&member_def.noexcept) {
+            qualifiers.push("noexcept".to_string());
+        } else if let Some(ref exceptions) = member_def.exceptions {
+            let exceptions = collapse_lines(&render_linked_text_type(exceptions));
+            let exceptions = exceptions.trim();
+            if !exceptions.is_empty() {
+                qualifiers.push(exceptions.to_string());
+            }
+        }
+    }
+
+    let specifier_position = find_pure_specifier(&rest);
+
+    // The virt specifiers go before any pure, default or delete specifier
+    if is_yes(&member_def.final_) && !has_word(&rest, "final") {
+        match specifier_position {
+            Some(position) => rest.insert_str(position, "final "),
+            None => rest = format!("{rest} final"),
+        }
+    }
+
+    if member_def.virt == Some(e::DoxVirtualKind::PureVirtual) && specifier_position.is_none() {
+        rest = format!("{rest} = 0");
+    }
+
+    if let Some(ref requiresclause) = member_def.requiresclause {
+        let requiresclause = collapse_lines(&render_linked_text_type(requiresclause));
+        if !has_word(&rest, "requires") {
+            rest = format!("{rest} requires {}", requiresclause.trim());
+        }
+    }
+
+    [
+        params.to_string(),
+        qualifiers.join(" "),
+        rest.trim().to_string(),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Returns the position after the parenthesis which closes the parameter list
+fn find_params_end(argsstring: &str) -> Option<usize> {
+    if !argsstring.starts_with('(') {
+        return None;
+    }
+
+    let mut depth = 0;
+    for (index, char) in argsstring.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Splits off the cv, ref and exception qualifiers at the start of the text after the parameter list. An exception
+/// specifier with arguments, eg. 'noexcept(false)', is kept as a single qualifier
+fn split_leading_qualifiers(tail: &str) -> (Vec<String>, &str) {
+    let mut qualifiers = Vec::new();
+    let mut rest = tail.trim_start();
+
+    loop {
+        if let Some(remaining) = rest.strip_prefix("&&") {
+            qualifiers.push("&&".to_string());
+            rest = remaining.trim_start();
+            continue;
+        }
+
+        if let Some(remaining) = rest.strip_prefix('&') {
+            qualifiers.push("&".to_string());
+            rest = remaining.trim_start();
+            continue;
+        }
+
+        let word_end = rest
+            .find(|char: char| !(char.is_alphanumeric() || char == '_'))
+            .unwrap_or(rest.len());
+
+        match &rest[..word_end] {
+            "const" | "volatile" => {
+                qualifiers.push(rest[..word_end].to_string());
+                rest = rest[word_end..].trim_start();
+            }
+            "noexcept" | "throw" => {
+                let after_word = &rest[word_end..];
+                let end = match find_params_end(after_word.trim_start()) {
+                    Some(params_end) => {
+                        word_end + (after_word.len() - after_word.trim_start().len()) + params_end
+                    }
+                    None => word_end,
+                };
+                qualifiers.push(rest[..end].to_string());
+                rest = rest[end..].trim_start();
+            }
+            _ => break,
+        }
+    }
+
+    (qualifiers, rest)
+}
+
+/// Returns the position of a trailing '= 0', '= default' or '= delete' specifier. Comparisons in a trailing
+/// requires-clause, eg. 'requires N == 0', aren't specifiers
+fn find_pure_specifier(str: &str) -> Option<usize> {
+    let is_identifier = |char: char| char.is_alphanumeric() || char == '_';
+
+    let str = str.trim_end();
+    let before = ["0", "default", "delete"]
+        .into_iter()
+        .filter_map(|specifier| str.strip_suffix(specifier))
+        .find(|before| !before.ends_with(is_identifier))?
+        .trim_end();
+
+    let position = before.strip_suffix('=')?.len();
+    let is_comparison = before[..position].ends_with(['=', '!', '<', '>']);
+
+    (!is_comparison).then_some(position)
+}
+
+fn has_word(str: &str, word: &str) -> bool {
+    str.split(|char: char| !(char.is_alphanumeric() || char == '_'))
+        .any(|part| part == word)
+}
+
+/// Returns the position of the first occurrence of the word which isn't part of a longer identifier
+fn find_word(str: &str, word: &str) -> Option<usize> {
+    let is_identifier = |char: char| char.is_alphanumeric() || char == '_';
+
+    str.match_indices(word)
+        .find(|(position, _)| {
+            let before = str[..*position].chars().next_back();
+            let after = str[position + word.len()..].chars().next();
+            !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier)
+        })
+        .map(|(position, _)| position)
+}
+
+/// Renders the width of a bitfield member, eg. ': 3'
+pub fn render_bitfield(bitfield: &str) -> String {
+    format!(": {}", bitfield.trim())
+}
+
+/// Renders the initializer of a variable or the value of a define on a single line, truncated to the maximum length
+/// if there is one. Variable initializers include the leading '=' or braces
+pub fn render_initializer(initializer: &e::LinkedTextType, max_length: Option<usize>) -> String {
+    let initializer = collapse_lines(&render_linked_text_type(initializer));
+    let initializer = initializer.trim();
+
+    match max_length {
+        Some(max_length) if initializer.chars().count() > max_length => {
+            let truncated: String = initializer.chars().take(max_length).collect();
+            format!("{} ...", truncated.trim_end())
+        }
+        _ => initializer.to_string(),
+    }
+}
+
 pub fn render_enum_value(
     domain: &Domain,
     enum_name_or_anon_id: &str,
@@ -251,3 +644,179 @@ fn render_ref_text_type(ref_text_type: &e::RefTextType) -> String {
     // on prepping text for signatures for Sphinx domain arguments
     html_escape::decode_html_entities(&ref_text_type.content).to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn linked_text(text: &str) -> e::LinkedTextType {
+        e::LinkedTextType {
+            content: vec![e::LinkedTextTypeItem::Text(text.to_string())],
+        }
+    }
+
+    fn parse_compound_def(compound_def: &str) -> e::CompounddefType {
+        e::parse(&format!(
+            r#"<doxygen version="1.9.7">{compound_def}</doxygen>"#
+        ))
+        .unwrap()
+        .compounddef
+        .unwrap()
+    }
+
+    #[test]
+    fn find_pure_specifier_at_end() {
+        assert_eq!(find_pure_specifier(" = 0"), Some(1));
+        assert_eq!(find_pure_specifier("=0"), Some(0));
+        assert_eq!(find_pure_specifier("override = default "), Some(9));
+        assert_eq!(find_pure_specifier("= delete"), Some(0));
+    }
+
+    #[test]
+    fn find_pure_specifier_ignores_comparisons() {
+        assert_eq!(find_pure_specifier("requires N == 0"), None);
+        assert_eq!(find_pure_specifier("requires (N == 0)"), None);
+        assert_eq!(find_pure_specifier("requires N != 0"), None);
+        assert_eq!(find_pure_specifier("requires N >= 0"), None);
+        assert_eq!(find_pure_specifier("requires N <= 0"), None);
+    }
+
+    #[test]
+    fn find_pure_specifier_ignores_other_values() {
+        assert_eq!(find_pure_specifier("= 10"), None);
+        assert_eq!(find_pure_specifier("= undefault"), None);
+        assert_eq!(find_pure_specifier("override"), None);
+        assert_eq!(find_pure_specifier(""), None);
+    }
+
+    #[test]
+    fn split_leading_qualifiers_cv_and_ref() {
+        assert_eq!(
+            split_leading_qualifiers(" const volatile && override"),
+            (
+                vec![
+                    "const".to_string(),
+                    "volatile".to_string(),
+                    "&&".to_string()
+                ],
+                "override"
+            )
+        );
+        assert_eq!(
+            split_leading_qualifiers("const& = delete"),
+            (vec!["const".to_string(), "&".to_string()], "= delete")
+        );
+    }
+
+    #[test]
+    fn split_leading_qualifiers_exception_specifiers() {
+        assert_eq!(
+            split_leading_qualifiers("noexcept(sizeof(T) > 4) final"),
+            (vec!["noexcept(sizeof(T) > 4)".to_string()], "final")
+        );
+        assert_eq!(
+            split_leading_qualifiers("const throw ()"),
+            (vec!["const".to_string(), "throw ()".to_string()], "")
+        );
+    }
+
+    #[test]
+    fn split_leading_qualifiers_stops_at_other_words() {
+        assert_eq!(
+            split_leading_qualifiers("constexpr_value const"),
+            (Vec::new(), "constexpr_value const")
+        );
+    }
+
+    #[test]
+    fn render_initializer_without_limit() {
+        assert_eq!(
+            render_initializer(&linked_text(" = {\n  1, 2\n}"), None),
+            "= {   1, 2 }"
+        );
+        assert_eq!(render_initializer(&linked_text("&lt;a&gt;"), None), "<a>");
+    }
+
+    #[test]
+    fn render_initializer_truncates_long_values() {
+        assert_eq!(
+            render_initializer(&linked_text("= 1234567890"), Some(6)),
+            "= 1234 ..."
+        );
+        assert_eq!(
+            render_initializer(&linked_text("= 1, 2"), Some(3)),
+            "= 1 ..."
+        );
+        assert_eq!(render_initializer(&linked_text("= 1"), Some(3)), "= 1");
+    }
+
+    #[test]
+    fn render_bitfield_width() {
+        assert_eq!(render_bitfield("3"), ": 3");
+        assert_eq!(render_bitfield(" 12 "), ": 12");
+    }
+
+    #[test]
+    fn find_word_skips_longer_identifiers() {
+        assert_eq!(find_word("concepts concept C", "concept"), Some(9));
+        assert_eq!(find_word("my_concept = true", "concept"), None);
+        assert_eq!(find_word("concept", "concept"), Some(0));
+    }
+
+    #[test]
+    fn render_concept_constraint_from_whole_definition() {
+        assert_eq!(
+            render_concept_constraint(&linked_text(
+                "template<typename T>\nconcept Number = std::is_arithmetic_v<T>"
+            )),
+            "std::is_arithmetic_v<T>"
+        );
+    }
+
+    #[test]
+    fn render_concept_constraint_from_expression() {
+        assert_eq!(
+            render_concept_constraint(&linked_text("= sizeof(T) == 4")),
+            "sizeof(T) == 4"
+        );
+        assert_eq!(
+            render_concept_constraint(&linked_text("std::integral<T>")),
+            "std::integral<T>"
+        );
+    }
+
+    #[test]
+    fn render_compound_def_for_concept() {
+        let compound_def = parse_compound_def(
+            r#"<compounddef id="concept_number" kind="concept" language="C++">
+                <compoundname>Number</compoundname>
+                <templateparamlist><param><type>typename T</type></param></templateparamlist>
+                <initializer>template&lt;typename T&gt;
+concept Number = std::is_arithmetic_v&lt;T&gt;</initializer>
+            </compounddef>"#,
+        );
+
+        assert_eq!(
+            render_compound_def(&Domain::CPlusPlus, &compound_def),
+            "template<typename T> Number = std::is_arithmetic_v<T>"
+        );
+    }
+
+    #[test]
+    fn render_compound_def_with_requires_clause() {
+        let compound_def = parse_compound_def(
+            r#"<compounddef id="class_vector" kind="class" language="C++" prot="public">
+                <compoundname>Vector</compoundname>
+                <templateparamlist><param><type>typename T</type></param></templateparamlist>
+                <requiresclause>Number&lt;T&gt;</requiresclause>
+            </compounddef>"#,
+        );
+
+        assert_eq!(
+            render_compound_def(&Domain::CPlusPlus, &compound_def),
+            "template<typename T> requires Number<T> Vector"
+        );
+    }
+}
@@ -4,6 +4,7 @@ mod xml;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use pyo3::exceptions::PyValueError;
@@ -11,49 +12,179 @@ use pyo3::prelude::*;
 
 use crate::cache::{Cache, FileCache, TrackedCache};
 use crate::doxygen::compound::generated as compound;
+use crate::doxygen::dot::GraphLimits;
+use crate::doxygen::filter::{Filter, Selection};
 use crate::doxygen::index::generated as index;
 use crate::doxygen::nodes::{Domain, Node};
-use crate::doxygen::render::Skip;
+use crate::doxygen::render::{
+    GraphKind, ImplementationSource, InbodyDescriptions, InheritedMembers, MemberExamples,
+    ParameterFormat, ReferenceKind, SortStrategy, SourceLocation,
+};
 
 #[pyclass]
 struct Context {
     pub project_root: PathBuf,
-    pub skip_settings: Vec<Skip>,
+    pub filter: Filter,
     pub domain_by_extension: HashMap<String, Domain>,
+    pub parameter_format: ParameterFormat,
+    pub show_derived_classes: bool,
+    pub graphs: Vec<GraphKind>,
+    pub graph_limits: GraphLimits,
+    pub sort: SortStrategy,
+    pub inherit_docs: bool,
+    pub show_reimplements: bool,
+    pub references: Vec<ReferenceKind>,
+    pub inbody_descriptions: InbodyDescriptions,
+    pub initializer_max_length: Option<usize>,
+    pub source_location: SourceLocation,
+    pub implementation_source: ImplementationSource,
+    pub listing_line_numbers: bool,
+    pub member_examples: bool,
 }
 
 #[pymethods]
 impl Context {
     #[new]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         project_root: String,
         skip_settings: Vec<String>,
         domain_by_extension: HashMap<String, String>,
+        parameter_format: String,
+        show_derived_classes: bool,
+        graphs: Vec<String>,
+        sort: String,
+        inherit_docs: bool,
+        show_reimplements: bool,
+        references: Vec<String>,
+        inbody_descriptions: String,
+        source_location: String,
+        implementation_source: String,
+        listing_line_numbers: bool,
+        member_examples: bool,
+        graph_max_depth: Option<usize>,
+        graph_max_nodes: Option<usize>,
+        initializer_max_length: Option<usize>,
     ) -> PyResult<Self> {
         let domain_by_extension = Domain::create_lookup(domain_by_extension)
             .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
 
-        let skip_settings = skip_settings
-            .into_iter()
-            .map(|value| {
-                if value == "members:all_caps" {
-                    Ok(Skip::MembersAllCaps)
-                } else {
-                    match value.strip_prefix("xml-nodes:") {
-                        Some(node) => Ok(Skip::XmlNode(node.to_string())),
-                        None => Err(format!("Unrecognised skip setting: {value}")),
-                    }
-                }
-            })
+        let filter = Filter::from_settings(&skip_settings)
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let parameter_format = parameter_format
+            .parse::<ParameterFormat>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let graphs = graphs
+            .iter()
+            .map(|value| value.parse::<GraphKind>())
             .collect::<Result<Vec<_>, _>>()
-            .map_err(PyValueError::new_err)?;
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let references = references
+            .iter()
+            .map(|value| value.parse::<ReferenceKind>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let inbody_descriptions = inbody_descriptions
+            .parse::<InbodyDescriptions>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let source_location = source_location
+            .parse::<SourceLocation>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let implementation_source = implementation_source
+            .parse::<ImplementationSource>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        let sort = sort
+            .parse::<SortStrategy>()
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
 
         Ok(Self {
             project_root: PathBuf::from(project_root),
-            skip_settings,
+            filter,
             domain_by_extension,
+            parameter_format,
+            show_derived_classes,
+            graphs,
+            graph_limits: GraphLimits {
+                max_depth: graph_max_depth,
+                max_nodes: graph_max_nodes,
+            },
+            sort,
+            inherit_docs,
+            show_reimplements,
+            references,
+            inbody_descriptions,
+            initializer_max_length,
+            source_location,
+            implementation_source,
+            listing_line_numbers,
+            member_examples,
         })
     }
+
+    /// Descriptions of the skip settings which can never match anything
+    #[getter]
+    fn filter_warnings(&self) -> Vec<String> {
+        self.filter.warnings().to_vec()
+    }
+}
+
+/// The members and sections of a compound to render for a particular directive
+#[pyclass]
+struct MemberSelection {
+    pub selection: Selection,
+}
+
+#[pymethods]
+impl MemberSelection {
+    #[new]
+    fn new(
+        exclude_members: Vec<String>,
+        members: Option<Vec<String>>,
+        sections: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let selection = Selection::new(members, sections, exclude_members)
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
+        Ok(Self { selection })
+    }
+}
+
+impl Context {
+    /// Creates the initial context for the render functions
+    fn render_context(&self) -> doxygen::render::Context {
+        doxygen::render::Context {
+            project_root: self.project_root.clone(),
+            domain: None,
+            filter: self.filter.clone(),
+            selection: Selection::default(),
+            extension_domain_lookup: self.domain_by_extension.clone(),
+            enumerated_list_depth: 0,
+            parameter_format: self.parameter_format.clone(),
+            parameter_types: HashMap::new(),
+            show_derived_classes: self.show_derived_classes,
+            graphs: self.graphs.clone(),
+            graph_limits: self.graph_limits.clone(),
+            sort: self.sort.clone(),
+            inherited_members: InheritedMembers::default(),
+            inherit_docs: self.inherit_docs,
+            show_reimplements: self.show_reimplements,
+            references: self.references.clone(),
+            inbody_descriptions: self.inbody_descriptions.clone(),
+            initializer_max_length: self.initializer_max_length,
+            source_location: self.source_location.clone(),
+            implementation_source: self.implementation_source.clone(),
+            language: None,
+            listing_line_numbers: self.listing_line_numbers,
+            member_examples: self.member_examples,
+        }
+    }
 }
 
 #[pyfunction]
@@ -61,9 +192,15 @@ fn render_class(
     name: String,
     path: String,
     context: &Context,
+    selection: &MemberSelection,
+    inherited_members: String,
     cache: &TrackedCache,
 ) -> PyResult<Vec<Node>> {
     tracing::info!("render_class {} {}", name, path);
+    let inherited_members = inherited_members
+        .parse::<InheritedMembers>()
+        .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
     let xml_directory = PathBuf::from(path);
 
     let cwd = std::env::current_dir()?;
@@ -85,13 +222,10 @@ fn render_class(
             let compound_xml_path = std::fs::canonicalize(xml_path.join(format!("{ref_id}.xml")))?;
             let root = cache.parse_compound(compound_xml_path)?;
 
-            let context = doxygen::render::Context {
-                project_root: context.project_root.clone(),
-                domain: None,
-                skip: context.skip_settings.clone(),
-                extension_domain_lookup: context.domain_by_extension.clone(),
-                enumerated_list_depth: 0,
-            };
+            let context = context
+                .render_context()
+                .with_selection(selection.selection.clone())
+                .with_inherited_members(inherited_members);
             let inner_groups = false;
             doxygen::render::render_compound(&context, root.as_ref(), inner_groups, &mut xml_loader)
                 .map_err(|err| PyValueError::new_err(format!("{}", err)))
@@ -107,9 +241,15 @@ fn render_struct(
     name: String,
     path: String,
     context: &Context,
+    selection: &MemberSelection,
+    inherited_members: String,
     cache: &TrackedCache,
 ) -> PyResult<Vec<Node>> {
     tracing::info!("render_struct {} {}", name, path);
+    let inherited_members = inherited_members
+        .parse::<InheritedMembers>()
+        .map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+
     let xml_directory = PathBuf::from(path);
 
     let cwd = std::env::current_dir()?;
@@ -131,13 +271,10 @@ fn render_struct(
             let compound_xml_path = std::fs::canonicalize(xml_path.join(format!("{ref_id}.xml")))?;
             let root = cache.parse_compound(compound_xml_path)?;
 
-            let context = doxygen::render::Context {
-                project_root: context.project_root.clone(),
-                domain: None,
-                skip: context.skip_settings.clone(),
-                extension_domain_lookup: context.domain_by_extension.clone(),
-                enumerated_list_depth: 0,
-            };
+            let context = context
+                .render_context()
+                .with_selection(selection.selection.clone())
+                .with_inherited_members(inherited_members);
 
             let inner_groups = false;
             doxygen::render::render_compound(&context, root.as_ref(), inner_groups, &mut xml_loader)
@@ -149,6 +286,93 @@ fn render_struct(
     }
 }
 
+#[pyfunction]
+fn render_concept(
+    name: String,
+    path: String,
+    context: &Context,
+    cache: &TrackedCache,
+) -> PyResult<Vec<Node>> {
+    tracing::info!("render_concept {} {}", name, path);
+    let xml_directory = PathBuf::from(path);
+
+    let cwd = std::env::current_dir()?;
+    let xml_path = cwd.join(xml_directory);
+
+    let mut xml_loader = XmlLoader::new(xml_path, (*cache).clone());
+    let compound_ref_id = {
+        let index = xml_loader.load_index()?;
+
+        index
+            .compound
+            .iter()
+            .find(|compound| compound.name == name && compound.kind == index::CompoundKind::Concept)
+            .map(|compound| compound.refid.clone())
+    };
+
+    match compound_ref_id {
+        Some(compound_ref_id) => {
+            let root = xml_loader.load(&compound_ref_id)?;
+
+            let inner_groups = false;
+            doxygen::render::render_compound(
+                &context.render_context(),
+                root.as_ref(),
+                inner_groups,
+                &mut xml_loader,
+            )
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))
+        }
+        None => Err(PyValueError::new_err(format!(
+            "Unable to find concept matching '{name}'"
+        ))),
+    }
+}
+
+#[pyfunction]
+fn render_file(
+    name: String,
+    path: String,
+    context: &Context,
+    selection: &MemberSelection,
+    cache: &TrackedCache,
+) -> PyResult<Vec<Node>> {
+    tracing::info!("render_file {} {}", name, path);
+    let xml_directory = PathBuf::from(path);
+
+    let cwd = std::env::current_dir()?;
+    let xml_path = cwd.join(xml_directory);
+    let index_xml_path = std::fs::canonicalize(xml_path.join("index.xml"))?;
+
+    let mut xml_loader = XmlLoader::new(xml_path.clone(), cache.clone());
+
+    let index = cache.parse_index(index_xml_path)?;
+
+    let compound = index
+        .compound
+        .iter()
+        .find(|compound| compound.name == name && compound.kind == index::CompoundKind::File);
+
+    match compound {
+        Some(compound) => {
+            let ref_id = &compound.refid;
+            let compound_xml_path = std::fs::canonicalize(xml_path.join(format!("{ref_id}.xml")))?;
+            let root = cache.parse_compound(compound_xml_path)?;
+
+            let context = context
+                .render_context()
+                .with_selection(selection.selection.clone());
+
+            let inner_groups = false;
+            doxygen::render::render_compound(&context, root.as_ref(), inner_groups, &mut xml_loader)
+                .map_err(|err| PyValueError::new_err(format!("{}", err)))
+        }
+        None => Err(PyValueError::new_err(format!(
+            "Unable to find file matching '{name}'"
+        ))),
+    }
+}
+
 #[pyfunction]
 fn render_enum(
     name: String,
@@ -201,18 +425,14 @@ fn render_member(
             let compound_xml_path = std::fs::canonicalize(xml_path.join(format!("{ref_id}.xml")))?;
             let root = cache.parse_compound(compound_xml_path)?;
 
-            let context = doxygen::render::Context {
-                project_root: context.project_root.clone(),
-                domain: None,
-                skip: context.skip_settings.clone(),
-                extension_domain_lookup: context.domain_by_extension.clone(),
-                enumerated_list_depth: 0,
-            };
+            let context = context.render_context();
+            let mut xml_loader = XmlLoader::new(xml_path.clone(), cache.clone());
 
             Ok(doxygen::render::render_member(
                 &context,
                 root.as_ref(),
                 &member.refid,
+                &mut xml_loader,
             ))
         }
         None => Err(PyValueError::new_err(format!(
@@ -226,11 +446,20 @@ fn render_member(
 pub struct XmlLoader {
     root: PathBuf,
     cache: TrackedCache,
+    /// The examples which refer to each member, built on first use as it requires loading every example
+    member_examples: Option<Rc<MemberExamples>>,
+    /// The refids of the file compounds for the source file paths that we've looked up
+    file_refids: HashMap<String, Option<String>>,
 }
 
 impl XmlLoader {
     pub fn new(root: PathBuf, cache: TrackedCache) -> Self {
-        Self { root, cache }
+        Self {
+            root,
+            cache,
+            member_examples: None,
+            file_refids: HashMap::new(),
+        }
     }
 
     pub fn load_index(&mut self) -> anyhow::Result<Arc<index::DoxygenType>> {
@@ -242,6 +471,57 @@ impl XmlLoader {
         let xml_path = std::fs::canonicalize(self.root.join(format!("{ref_id}.xml")))?;
         self.cache.parse_compound(xml_path)
     }
+
+    /// Reads the source file, which is tracked as a dependency like the xml files
+    pub fn read_source(&mut self, path: PathBuf) -> anyhow::Result<Arc<String>> {
+        let path = std::fs::canonicalize(path)?;
+        self.cache.read_source(path)
+    }
+
+    /// Finds the refid of the file compound for the source file path. The index only provides the base name of each
+    /// file so we check the full path of each candidate
+    pub fn file_refid(&mut self, file: &str) -> Option<String> {
+        if let Some(refid) = self.file_refids.get(file) {
+            return refid.clone();
+        }
+
+        let base_name = file.rsplit('/').next().unwrap_or(file);
+        let candidates: Vec<String> = self
+            .load_index()
+            .map(|index| {
+                index
+                    .compound
+                    .iter()
+                    .filter(|compound| {
+                        compound.kind == index::CompoundKind::File && compound.name == base_name
+                    })
+                    .map(|compound| compound.refid.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let refid = candidates.into_iter().find(|refid| {
+            self.load(refid).is_ok_and(|root| {
+                root.compounddef
+                    .as_ref()
+                    .and_then(|compound_def| compound_def.location.as_ref())
+                    .is_some_and(|location| location.file == file)
+            })
+        });
+
+        self.file_refids.insert(file.to_string(), refid.clone());
+        refid
+    }
+
+    pub fn member_examples(&mut self) -> Rc<MemberExamples> {
+        if let Some(ref member_examples) = self.member_examples {
+            return member_examples.clone();
+        }
+
+        let member_examples = Rc::new(doxygen::render::collect_member_examples(self));
+        self.member_examples = Some(member_examples.clone());
+        member_examples
+    }
 }
 
 #[pyfunction]
@@ -249,6 +529,7 @@ fn render_group(
     name: String,
     path: String,
     context: &Context,
+    selection: &MemberSelection,
     content_only: bool,
     // TODO: Use 'filter' concept instead of passing this bool around
     inner_groups: bool,
@@ -277,13 +558,9 @@ fn render_group(
 
             tracing::debug!("Compound root: {root:?}");
 
-            let context = doxygen::render::Context {
-                project_root: context.project_root.clone(),
-                domain: None,
-                skip: context.skip_settings.clone(),
-                extension_domain_lookup: context.domain_by_extension.clone(),
-                enumerated_list_depth: 0,
-            };
+            let context = context
+                .render_context()
+                .with_selection(selection.selection.clone());
 
             if content_only {
                 let Some(ref compounddef) = root.compounddef else {
@@ -324,6 +601,133 @@ fn render_group(
     }
 }
 
+#[pyfunction]
+fn render_page(
+    name: String,
+    path: String,
+    context: &Context,
+    content_only: bool,
+    cache: &TrackedCache,
+) -> PyResult<Vec<Node>> {
+    tracing::info!("render_page {} {}", name, path);
+    let xml_directory = PathBuf::from(path);
+
+    let cwd = std::env::current_dir()?;
+    let xml_path = cwd.join(xml_directory);
+
+    let mut xml_loader = XmlLoader::new(xml_path, (*cache).clone());
+    let compound_ref_id = {
+        let index = xml_loader.load_index()?;
+
+        index
+            .compound
+            .iter()
+            .find(|compound| compound.name == name && compound.kind == index::CompoundKind::Page)
+            .map(|compound| compound.refid.clone())
+    };
+
+    match compound_ref_id {
+        Some(compound_ref_id) => {
+            let root = xml_loader.load(&compound_ref_id)?;
+
+            doxygen::render::render_page(
+                &context.render_context(),
+                root.as_ref(),
+                content_only,
+                &mut xml_loader,
+            )
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))
+        }
+        None => Err(PyValueError::new_err(format!(
+            "Unable to find page matching '{name}'"
+        ))),
+    }
+}
+
+#[pyfunction]
+fn render_example(
+    name: String,
+    path: String,
+    context: &Context,
+    content_only: bool,
+    cache: &TrackedCache,
+) -> PyResult<Vec<Node>> {
+    tracing::info!("render_example {} {}", name, path);
+    let xml_directory = PathBuf::from(path);
+
+    let cwd = std::env::current_dir()?;
+    let xml_path = cwd.join(xml_directory);
+
+    let mut xml_loader = XmlLoader::new(xml_path, (*cache).clone());
+    let compound_ref_id = {
+        let index = xml_loader.load_index()?;
+
+        index
+            .compound
+            .iter()
+            .find(|compound| compound.name == name && compound.kind == index::CompoundKind::Example)
+            .map(|compound| compound.refid.clone())
+    };
+
+    match compound_ref_id {
+        Some(compound_ref_id) => {
+            let root = xml_loader.load(&compound_ref_id)?;
+
+            doxygen::render::render_example(&context.render_context(), root.as_ref(), content_only)
+                .map_err(|err| PyValueError::new_err(format!("{}", err)))
+        }
+        None => Err(PyValueError::new_err(format!(
+            "Unable to find example matching '{name}'"
+        ))),
+    }
+}
+
+#[pyfunction]
+fn render_dir(
+    name: String,
+    path: String,
+    context: &Context,
+    cache: &TrackedCache,
+    depth: Option<usize>,
+) -> PyResult<Vec<Node>> {
+    tracing::info!("render_dir {} {}", name, path);
+    let xml_directory = PathBuf::from(path);
+
+    let cwd = std::env::current_dir()?;
+    let xml_path = cwd.join(xml_directory);
+
+    // Doxygen doesn't include a trailing slash in directory names but users might
+    let name = name.trim_end_matches('/');
+
+    let mut xml_loader = XmlLoader::new(xml_path, (*cache).clone());
+    let compound_ref_id = {
+        let index = xml_loader.load_index()?;
+
+        index
+            .compound
+            .iter()
+            .find(|compound| compound.name == name && compound.kind == index::CompoundKind::Dir)
+            .map(|compound| compound.refid.clone())
+    };
+
+    match compound_ref_id {
+        Some(compound_ref_id) => {
+            let root = xml_loader.load(&compound_ref_id)?;
+
+            doxygen::render::render_dir(
+                &context.render_context(),
+                root.as_ref(),
+                depth,
+                &mut xml_loader,
+            )
+            .map_err(|err| PyValueError::new_err(format!("{}", err)))
+        }
+        None => Err(PyValueError::new_err(format!(
+            "Unable to find directory matching '{name}'"
+        ))),
+    }
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn backend(_py: Python, module: &PyModule) -> PyResult<()> {
@@ -343,14 +747,20 @@ fn backend(_py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<FileCache>()?;
     module.add_class::<TrackedCache>()?;
     module.add_class::<Context>()?;
+    module.add_class::<MemberSelection>()?;
 
     module.add_wrapped(pyo3::wrap_pyfunction!(render_class))?;
     module.add_wrapped(pyo3::wrap_pyfunction!(render_struct))?;
+    module.add_wrapped(pyo3::wrap_pyfunction!(render_concept))?;
+    module.add_wrapped(pyo3::wrap_pyfunction!(render_file))?;
+    module.add_wrapped(pyo3::wrap_pyfunction!(render_dir))?;
 
     module.add_wrapped(pyo3::wrap_pyfunction!(render_function))?;
     module.add_wrapped(pyo3::wrap_pyfunction!(render_enum))?;
 
     module.add_wrapped(pyo3::wrap_pyfunction!(render_group))?;
+    module.add_wrapped(pyo3::wrap_pyfunction!(render_page))?;
+    module.add_wrapped(pyo3::wrap_pyfunction!(render_example))?;
 
     Ok(())
 }
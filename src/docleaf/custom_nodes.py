@@ -0,0 +1,79 @@
+from docutils import nodes
+
+from sphinx.application import Sphinx
+
+
+class details(nodes.General, nodes.Element):
+    """
+    Collapsible block which is rendered as a html5 <details> element. Other builders just render the children.
+    """
+
+    pass
+
+
+class details_summary(nodes.General, nodes.TextElement):
+    """
+    Always visible heading of a details block which is rendered as a html5 <summary> element. Other builders render
+    it as an emphasised paragraph so that it stays separate from the children of the details block.
+    """
+
+    pass
+
+
+def visit_details_html(self, node):
+    self.body.append(self.starttag(node, "details", CLASS="docleaf-details"))
+
+
+def depart_details_html(self, node):
+    self.body.append("</details>\n")
+
+
+def visit_details_summary_html(self, node):
+    self.body.append(self.starttag(node, "summary", ""))
+
+
+def depart_details_summary_html(self, node):
+    self.body.append("</summary>\n")
+
+
+def visit_details_summary_paragraph(self, node):
+    self.visit_paragraph(node)
+    self.visit_emphasis(node)
+
+
+def depart_details_summary_paragraph(self, node):
+    self.depart_emphasis(node)
+    self.depart_paragraph(node)
+
+
+details_summary_paragraph = (visit_details_summary_paragraph, depart_details_summary_paragraph)
+
+
+def visit_passthrough(self, node):
+    pass
+
+
+def depart_passthrough(self, node):
+    pass
+
+
+passthrough = (visit_passthrough, depart_passthrough)
+
+
+def setup(app: Sphinx):
+    app.add_node(
+        details,
+        html=(visit_details_html, depart_details_html),
+        latex=passthrough,
+        text=passthrough,
+        man=passthrough,
+        texinfo=passthrough,
+    )
+    app.add_node(
+        details_summary,
+        html=(visit_details_summary_html, depart_details_summary_html),
+        latex=details_summary_paragraph,
+        text=details_summary_paragraph,
+        man=details_summary_paragraph,
+        texinfo=details_summary_paragraph,
+    )
@@ -31,6 +31,7 @@ def chain(*funcs):
 
 cpp_domain = {
     "class": (cpp.CPPClassObject, "class", add_location_via_names),
+    "concept": (cpp.CPPConceptObject, "concept", add_location_via_names),
     "enum": (cpp.CPPEnumObject, "enum", add_location_via_names),
     "enumerator": (
         cpp.CPPEnumeratorObject,
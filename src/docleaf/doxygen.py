@@ -18,13 +18,15 @@ from sphinx.util.nodes import nested_parse_with_titles
 from sphinx.util import logging
 import sphinx.addnodes
 
-from . import backend, domains, copied
+from . import backend, domains, copied, custom_nodes, graphs, listings
 from .errors import DocleafError
 
 __version__ = "0.0.0"
 
 logger = logging.getLogger(__name__)
 
+reported_filter_warnings = set()
+
 
 class GitHubLinkResolver:
     """
@@ -81,9 +83,17 @@ class NodeManager:
         self.state = state
         self.directive_arguments = directive_arguments
         self.lookup = {
+            "admonition": as_list(nodes.admonition),
+            "attention": as_list(nodes.attention),
+            "block_quote": as_list(nodes.block_quote),
             "bullet_list": as_list(nodes.bullet_list),
+            "compound": as_list(nodes.compound),
             "container": as_list(nodes.container),
+            "code_block": self.build_code_block,
             "colspec": as_list(nodes.colspec),
+            "definition": as_list(nodes.definition),
+            "definition_list": as_list(nodes.definition_list),
+            "definition_list_item": as_list(nodes.definition_list_item),
             "desc": as_list(sphinx.addnodes.desc),
             "desc_content": as_list(sphinx.addnodes.desc_content),
             "desc_name": as_list(sphinx.addnodes.desc_name),
@@ -94,6 +104,8 @@ class NodeManager:
             "desc_sig_space": as_list(sphinx.addnodes.desc_sig_space),
             "desc_signature": as_list(sphinx.addnodes.desc_signature),
             "desc_signature_line": as_list(sphinx.addnodes.desc_signature_line),
+            "details": as_list(custom_nodes.details),
+            "details_summary": as_list(custom_nodes.details_summary),
             "emphasis": as_list(nodes.emphasis),
             "entry": as_list(nodes.entry),
             "enumerated_list": as_list(nodes.enumerated_list),
@@ -101,6 +113,7 @@ class NodeManager:
             "field": as_list(nodes.field),
             "field_name": as_list(nodes.field_name),
             "field_body": as_list(nodes.field_body),
+            "graphviz": self.build_graphviz,
             "index": as_list(sphinx.addnodes.index),
             "inline": as_list(nodes.inline),
             "list_item": as_list(nodes.list_item),
@@ -112,17 +125,21 @@ class NodeManager:
             "paragraph": as_list(nodes.paragraph),
             "raw": as_list(nodes.raw),
             "internal_reference": self.build_internal_reference,
+            "optional_reference": self.build_optional_reference,
             "external_reference": as_list(nodes.reference),
             "restructured_text_block": self.build_restructured_text_block,
             "restructured_text_inline": self.build_restructured_text_inline,
             "row": as_list(nodes.row),
             "rubric": as_list(nodes.rubric),
+            "section": self.build_section,
             "strong": as_list(nodes.strong),
             "see_also": as_list(sphinx.addnodes.seealso),
             "table": as_list(nodes.table),
             "tbody": as_list(nodes.tbody),
+            "term": as_list(nodes.term),
             "tgroup": as_list(nodes.tgroup),
             "thead": as_list(nodes.thead),
+            "title": as_list(nodes.title),
             "warning": as_list(nodes.warning),
             # Special
             "target": as_list(self.build_target),
@@ -138,6 +155,21 @@ class NodeManager:
         self.state.document.note_explicit_target(target)
         return target
 
+    def build_code_block(self, *children, **attributes):
+        return [
+            listings.build_code_block(
+                children,
+                attributes.get("language"),
+                attributes.get("line_number_start"),
+                attributes.get("highlighted_lines"),
+            )
+        ]
+
+    def build_section(self, *children, **attributes):
+        section = nodes.section("", *children, **attributes["target"])
+        self.state.document.note_explicit_target(section)
+        return [section]
+
     def build_domain_entry(self, *children, **attributes):
         return domains.render_domain_entry(
             attributes["domain"],
@@ -149,6 +181,15 @@ class NodeManager:
             children,
         )
 
+    def build_graphviz(self, **attributes):
+        graph = graphs.build_graphviz(
+            self.state.document.settings.env.docname,
+            attributes["code"],
+            attributes["alt"],
+            attributes["refids"],
+        )
+        return [graph]
+
     def build_internal_reference(self, *children, **attributes):
         reference = sphinx.addnodes.pending_xref(
             "",
@@ -161,6 +202,11 @@ class NodeManager:
         )
         return [reference]
 
+    def build_optional_reference(self, *children, **attributes):
+        [reference] = self.build_internal_reference(*children, **attributes)
+        reference["docleaf_optional"] = True
+        return [reference]
+
     def build_restructured_text_block(self, *children, **attributes):
         text = textwrap.dedent(children[0])
 
@@ -224,9 +270,11 @@ def render_node(node, node_manager):
 
 
 class Project:
-    def __init__(self, root, xml):
+    def __init__(self, root, xml, skip, settings):
         self._root = root
         self._xml = xml
+        self._skip = skip
+        self._settings = settings
 
     def root(self):
         return self._root
@@ -234,6 +282,18 @@ class Project:
     def xml(self):
         return self._xml
 
+    def skip(self):
+        return self._skip
+
+    def setting(self, app, name: str):
+        """
+        The value of the project's entry for the setting if it has one, otherwise the value of the 'docleaf_<name>'
+        config variable
+        """
+        if name in self._settings:
+            return self._settings[name]
+        return getattr(app.config, f"docleaf_{name}")
+
     def get(projects, name: str):
         # For each 'try' block we need to catch KeyError and TypeError (if project is a string) so we catch everything
         # as there isn't much else that could go wrong
@@ -261,7 +321,9 @@ class Project:
                 "config variable"
             )
 
-        return Project(root, xml)
+        skip = data.get("skip", [])
+
+        return Project(root, xml, skip, data)
 
 
 class BaseDirective(Directive):
@@ -287,37 +349,77 @@ class BasicDoxygenDirective(BaseDirective):
     final_argument_whitespace = True
     option_spec = {
         "project": directives.unchanged,
+        "skip": directives.unchanged,
         "skip-xml-nodes": directives.unchanged,
     }
 
     def run(self) -> List[Node]:
         name = self.arguments[0]
-        project_name = self.options["project"] or self.app.config.docleaf_default_project
+        project_name = self.options.get("project", self.app.config.docleaf_default_project)
         project = Project.get(self.app.config.docleaf_projects, project_name)
-        skip_settings = get_skip_settings(self.app, self.options)
-
-        context = backend.Context(
-            project.root(),
-            skip_settings,
-            self.app.config.docleaf_domain_by_extension,
-        )
+        context = create_context(self.app, project, self.options)
 
         tracked_cache = backend.TrackedCache(self.cache)
         node_list = self.render_function(name, project.xml(), context, tracked_cache)
-        update_sphinx_env_file_data(self.app.env, tracked_cache.xml_paths(), self.app.env.docname)
+        update_sphinx_env_file_data(self.app.env, tracked_cache.dependency_paths(), self.app.env.docname)
 
         node_builder = NodeManager(self.state, self.get_directive_args())
         return render_node_list(node_list, node_builder)
 
 
-class ClassDirective(BasicDoxygenDirective):
+class CompoundDirective(BasicDoxygenDirective):
+    option_spec = {
+        **BasicDoxygenDirective.option_spec,
+        "members": directives.unchanged,
+        "exclude-members": directives.unchanged,
+        "sections": directives.unchanged,
+        "sort": directives.unchanged,
+    }
+
+    def run(self) -> List[Node]:
+        name = self.arguments[0]
+        project_name = self.options.get("project", self.app.config.docleaf_default_project)
+        project = Project.get(self.app.config.docleaf_projects, project_name)
+        context = create_context(self.app, project, self.options)
+        selection = create_member_selection(self.options)
+
+        tracked_cache = backend.TrackedCache(self.cache)
+        node_list = self.render(name, project.xml(), context, selection, tracked_cache)
+        update_sphinx_env_file_data(self.app.env, tracked_cache.dependency_paths(), self.app.env.docname)
+
+        node_builder = NodeManager(self.state, self.get_directive_args())
+        return render_node_list(node_list, node_builder)
+
+    def render(self, name, xml, context, selection, tracked_cache):
+        return self.render_function(name, xml, context, selection, tracked_cache)
+
+
+class ClassDirective(CompoundDirective):
+    option_spec = {
+        **CompoundDirective.option_spec,
+        "inherited-members": directives.unchanged,
+    }
+
     render_function = backend.render_class
 
+    def render(self, name, xml, context, selection, tracked_cache):
+        default_inherited_members = self.app.config.docleaf_inherited_members
+        inherited_members = self.options.get("inherited-members", default_inherited_members)
+        return self.render_function(name, xml, context, selection, inherited_members, tracked_cache)
+
 
-class StructDirective(BasicDoxygenDirective):
+class StructDirective(ClassDirective):
     render_function = backend.render_struct
 
 
+class FileDirective(CompoundDirective):
+    render_function = backend.render_file
+
+
+class ConceptDirective(BasicDoxygenDirective):
+    render_function = backend.render_concept
+
+
 class EnumDirective(BasicDoxygenDirective):
     render_function = backend.render_enum
 
@@ -335,7 +437,12 @@ class GroupDirective(BaseDirective):
         "project": directives.unchanged,
         "content-only": directives.flag,  # TODO: Implement
         "inner": directives.flag,  # TODO: Implement
+        "skip": directives.unchanged,
         "skip-xml-nodes": directives.unchanged,
+        "members": directives.unchanged,
+        "exclude-members": directives.unchanged,
+        "sections": directives.unchanged,
+        "sort": directives.unchanged,
     }
 
     def run(self) -> List[Node]:
@@ -343,39 +450,156 @@ class GroupDirective(BaseDirective):
         project_name = self.options.get("project", self.app.config.docleaf_default_project)
         project = Project.get(self.app.config.docleaf_projects, project_name)
 
-        skip_settings = get_skip_settings(self.app, self.options)
         content_only = "content-only" in self.options
         inner_group = "inner" in self.options
-        context = backend.Context(
-            project.root(),
-            skip_settings,
-            self.app.config.docleaf_domain_by_extension,
-        )
+        context = create_context(self.app, project, self.options)
+        selection = create_member_selection(self.options)
 
         tracked_cache = backend.TrackedCache(self.cache)
         node_list = backend.render_group(
             name,
             project.xml(),
             context,
+            selection,
             content_only,
             inner_group,
             tracked_cache,
         )
-        update_sphinx_env_file_data(self.app.env, tracked_cache.xml_paths(), self.app.env.docname)
+        update_sphinx_env_file_data(self.app.env, tracked_cache.dependency_paths(), self.app.env.docname)
+
+        node_builder = NodeManager(self.state, self.get_directive_args())
+        return render_node_list(node_list, node_builder)
+
+
+class DirDirective(BaseDirective):
+    has_content = True
+    required_arguments = 1
+    optional_arguments = 0
+    final_argument_whitespace = True
+    option_spec = {
+        "project": directives.unchanged,
+        "depth": directives.nonnegative_int,
+        "skip": directives.unchanged,
+        "skip-xml-nodes": directives.unchanged,
+    }
+
+    def run(self) -> List[Node]:
+        name = self.arguments[0]
+        project_name = self.options.get("project", self.app.config.docleaf_default_project)
+        project = Project.get(self.app.config.docleaf_projects, project_name)
+
+        depth = self.options.get("depth", self.app.config.docleaf_dir_depth)
+        context = create_context(self.app, project, self.options)
+
+        tracked_cache = backend.TrackedCache(self.cache)
+        node_list = backend.render_dir(name, project.xml(), context, tracked_cache, depth)
+        update_sphinx_env_file_data(self.app.env, tracked_cache.dependency_paths(), self.app.env.docname)
 
         node_builder = NodeManager(self.state, self.get_directive_args())
         return render_node_list(node_list, node_builder)
 
 
-def get_skip_settings(app, options):
+class PageDirective(BaseDirective):
+    render_function = backend.render_page
+
+    has_content = True
+    required_arguments = 1
+    optional_arguments = 0
+    final_argument_whitespace = True
+    option_spec = {
+        "project": directives.unchanged,
+        "content-only": directives.flag,
+        "skip": directives.unchanged,
+        "skip-xml-nodes": directives.unchanged,
+    }
+
+    def run(self) -> List[Node]:
+        name = self.arguments[0]
+        project_name = self.options.get("project", self.app.config.docleaf_default_project)
+        project = Project.get(self.app.config.docleaf_projects, project_name)
+
+        content_only = "content-only" in self.options
+        context = create_context(self.app, project, self.options)
+
+        tracked_cache = backend.TrackedCache(self.cache)
+        node_list = self.render_function(name, project.xml(), context, content_only, tracked_cache)
+        update_sphinx_env_file_data(self.app.env, tracked_cache.dependency_paths(), self.app.env.docname)
+
+        node_builder = NodeManager(self.state, self.get_directive_args())
+        return render_node_list(node_list, node_builder)
+
+
+class ExampleDirective(PageDirective):
+    render_function = backend.render_example
+
+
+def create_context(app, project, options):
+    """
+    Create the backend context from the app config and the directive options
     """
-    Get the option for the directive and fallback to the app option if not defined on the directive
+    context = backend.Context(
+        project.root(),
+        get_skip_settings(app, project, options),
+        app.config.docleaf_domain_by_extension,
+        app.config.docleaf_parameter_format,
+        app.config.docleaf_show_derived_classes,
+        app.config.docleaf_graphs,
+        options.get("sort", app.config.docleaf_sort),
+        app.config.docleaf_inherit_docs,
+        app.config.docleaf_show_reimplements,
+        list(project.setting(app, "references")),
+        project.setting(app, "inbody_descriptions"),
+        app.config.docleaf_source_location,
+        project.setting(app, "implementation_source"),
+        app.config.docleaf_listing_line_numbers,
+        app.config.docleaf_member_examples,
+        app.config.docleaf_graph_max_depth,
+        app.config.docleaf_graph_max_nodes,
+        app.config.docleaf_initializer_max_length,
+    )
+
+    # Only report each problem once as the same settings are used for every directive
+    for warning in context.filter_warnings:
+        if warning not in reported_filter_warnings:
+            reported_filter_warnings.add(warning)
+            logger.warning(f"docleaf: {warning}")
+
+    return context
+
+
+def create_member_selection(options):
     """
-    skip_settings = options.get("skip", None)
-    if skip_settings is None:
-        skip_settings = app.config.docleaf_doxygen_skip
+    Create the backend member selection from the directive options. A ':members:' or ':sections:' option without a
+    value selects everything, in the same manner as Breathe
+    """
+    return backend.MemberSelection(
+        split_option(options.get("exclude-members")) or [],
+        split_option(options.get("members")) or None,
+        split_option(options.get("sections")) or None,
+    )
+
+
+def split_option(value):
+    if value is None:
+        return None
+    return [entry.strip() for entry in value.split(",") if entry.strip()]
+
+
+def get_skip_settings(app, project, options):
+    """
+    Get the skip rules from the directive options and fall back to the rules from the app config and the project
+    config if the directive doesn't have any. Nodes from the skip-xml-nodes option are always added
+    """
+    directive_skip_settings = options.get("skip", None)
+    if directive_skip_settings is None:
+        skip_settings = list(app.config.docleaf_doxygen_skip) + list(project.skip())
     else:
-        skip_settings = skip_settings.split(",")
+        skip_settings = [setting.strip() for setting in directive_skip_settings.split(",")]
+
+    skip_xml_nodes = options.get("skip-xml-nodes", None)
+    if skip_xml_nodes is not None:
+        skip_settings += [f"xml-nodes:{node.strip()}" for node in skip_xml_nodes.split(",")]
+
     return skip_settings
 
 
@@ -398,14 +622,14 @@ def hash_file(path):
         return hash.hexdigest()
 
 
-def update_sphinx_env_file_data(env: BuildEnvironment, xml_paths: List[str], rst_file: str):
+def update_sphinx_env_file_data(env: BuildEnvironment, dependency_paths: List[str], rst_file: str):
     """
-    Update our file_data store to indicate which rst files are dependent on which xml files
+    Update our file_data store to indicate which rst files are dependent on which xml files and source files
     """
     if not hasattr(env, "docleaf_file_data"):
         env.docleaf_file_data = {}
 
-    for path in xml_paths:
+    for path in dependency_paths:
         if path in env.docleaf_file_data:
             env.docleaf_file_data[path].rst_files.add(rst_file)
         else:
@@ -470,6 +694,17 @@ class ExtensionContext:
         self.cache = cache
 
 
+def resolve_missing_optional_reference(app: Sphinx, env, node: Node, contnode: Node):
+    """
+    Replace optional references whose targets aren't in the Sphinx documentation with their content as plain text
+    rather than warning about them
+    """
+    if node.get("docleaf_optional"):
+        return nodes.inline("", "", *node.children)
+
+    return None
+
+
 def add_directive(context, name, Cls):
     Cls.app = context.app
     Cls.cache = context.cache
@@ -481,18 +716,44 @@ def setup(app: Sphinx):
 
     context = ExtensionContext(app, cache)
 
+    custom_nodes.setup(app)
+    graphs.setup(app)
+
     add_directive(context, "doxygenclass", ClassDirective)
+    add_directive(context, "doxygenconcept", ConceptDirective)
+    add_directive(context, "doxygendir", DirDirective)
     add_directive(context, "doxygenenum", EnumDirective)
+    add_directive(context, "doxygenexample", ExampleDirective)
+    add_directive(context, "doxygenfile", FileDirective)
     add_directive(context, "doxygenfunction", FunctionDirective)
     add_directive(context, "doxygengroup", GroupDirective)
+    add_directive(context, "doxygenpage", PageDirective)
     add_directive(context, "doxygenstruct", StructDirective)
 
     app.add_config_value("docleaf_projects", {}, "env")
     app.add_config_value("docleaf_default_project", None, "env")
     app.add_config_value("docleaf_doxygen_skip", [], "env")
     app.add_config_value("docleaf_domain_by_extension", {}, True)
+    app.add_config_value("docleaf_parameter_format", "names", "env")
+    app.add_config_value("docleaf_show_derived_classes", False, "env")
+    app.add_config_value("docleaf_graphs", [], "env")
+    app.add_config_value("docleaf_graph_max_depth", None, "env")
+    app.add_config_value("docleaf_graph_max_nodes", None, "env")
+    app.add_config_value("docleaf_sort", "xml", "env")
+    app.add_config_value("docleaf_inherited_members", "none", "env")
+    app.add_config_value("docleaf_inherit_docs", False, "env")
+    app.add_config_value("docleaf_show_reimplements", False, "env")
+    app.add_config_value("docleaf_references", [], "env")
+    app.add_config_value("docleaf_inbody_descriptions", "none", "env")
+    app.add_config_value("docleaf_initializer_max_length", None, "env")
+    app.add_config_value("docleaf_source_location", "definition", "env")
+    app.add_config_value("docleaf_implementation_source", "none", "env")
+    app.add_config_value("docleaf_listing_line_numbers", False, "env")
+    app.add_config_value("docleaf_member_examples", False, "env")
+    app.add_config_value("docleaf_dir_depth", 1, "env")
 
     app.connect("env-get-outdated", calculate_files_to_refresh)
     app.connect("env-purge-doc", purge_file_data)
+    app.connect("missing-reference", resolve_missing_optional_reference)
 
     return {"version": __version__, "parallel_read_safe": True, "parallel_write_safe": True}
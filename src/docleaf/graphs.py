@@ -0,0 +1,47 @@
+from docutils import nodes
+
+from sphinx.application import Sphinx
+from sphinx.ext.graphviz import graphviz
+
+
+def build_graphviz(docname, code, alt, refids):
+    """
+    Create a sphinx.ext.graphviz node for the DOT source from the backend. The links in the source use the refids as
+    placeholder urls which are resolved once the targets are known
+    """
+    node = graphviz()
+    node["code"] = code
+    node["options"] = {"docname": docname}
+    node["alt"] = alt
+    node["docleaf_refids"] = refids
+    return node
+
+
+def resolve_graph_links(app: Sphinx, doctree: nodes.document, docname: str):
+    """
+    Replace the refid placeholder urls in any graphs with the relative urls of the targets. Nodes with targets that
+    aren't included in the Sphinx documentation have their links removed
+    """
+    std_domain = app.env.get_domain("std")
+
+    for node in doctree.traverse(graphviz):
+        refids = node.get("docleaf_refids")
+        if not refids:
+            continue
+
+        code = node["code"]
+        for refid in refids:
+            placeholder = f'URL="{refid}"'
+            target_docname, label_id = std_domain.anonlabels.get(refid, (None, None))
+            if target_docname is None:
+                code = code.replace(f", {placeholder}", "")
+            else:
+                uri = app.builder.get_relative_uri(docname, target_docname) + "#" + label_id
+                code = code.replace(placeholder, f'URL="{uri}"')
+
+        node["code"] = code
+
+
+def setup(app: Sphinx):
+    app.setup_extension("sphinx.ext.graphviz")
+    app.connect("doctree-resolved", resolve_graph_links)
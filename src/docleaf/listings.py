@@ -0,0 +1,129 @@
+from docutils import nodes
+
+from pygments.lexers import get_lexer_by_name
+from pygments.token import STANDARD_TYPES
+from pygments.util import ClassNotFound
+
+
+def build_code_block(children, language, line_number_start, highlighted_lines):
+    """
+    Create a literal block for a program listing. Sphinx only highlights literal blocks which contain plain text so
+    listings without links are passed to Sphinx to highlight as normal. Listings with links, which are most listings
+    from Doxygen, are highlighted here instead so that the links still work. We give the text the same classes that
+    Pygments uses within a 'highlight' container so that the theme's Pygments styles apply. The trade-off is that
+    this highlighting is only visible in HTML output as other builders ignore the classes
+    """
+    if all(isinstance(child, nodes.Text) for child in children):
+        text = "".join(children)
+        block = nodes.literal_block(text, text)
+
+        if language:
+            block["language"] = language
+
+        highlight_args = {}
+        if line_number_start is not None:
+            block["linenos"] = True
+            highlight_args["linenostart"] = line_number_start
+        if highlighted_lines:
+            highlight_args["hl_lines"] = highlighted_lines
+        if highlight_args:
+            block["highlight_args"] = highlight_args
+
+        return block
+
+    lines = highlight_lines(children, language)
+
+    block_children = []
+    width = len(str((line_number_start or 1) + len(lines) - 1))
+    for index, line in enumerate(lines):
+        if index > 0:
+            block_children.append(nodes.Text("\n"))
+
+        if line_number_start is not None:
+            number = f"{line_number_start + index:>{width}} "
+            block_children.append(nodes.inline(number, number, classes=["linenos"]))
+
+        if index + 1 in (highlighted_lines or []):
+            block_children.append(nodes.inline("", "", *line, classes=["hll"]))
+        else:
+            block_children.extend(line)
+
+    # Equivalent to the parsed-literal directive
+    block = nodes.literal_block("", "", *block_children)
+
+    container_classes = ["highlight"]
+    if language:
+        container_classes.append(f"highlight-{language}")
+
+    return nodes.container("", block, classes=container_classes)
+
+
+def highlight_lines(children, language):
+    """
+    Split the children into lines of nodes with each token of the text wrapped in an inline node with the Pygments
+    class for the token. Tokens within links are wrapped inside a copy of the link node
+    """
+    text = "".join(child.astext() for child in children)
+
+    # The start offset of each child in the text
+    segments = []
+    offset = 0
+    for child in children:
+        segments.append((offset, child))
+        offset += len(child.astext())
+
+    lines = [[]]
+    # The copy of the link that the previous part was added to, if any, so that consecutive parts of the same link
+    # share a single copy
+    link = None
+    segment_index = 0
+    position = 0
+    for token_type, value in lex(text, language):
+        token_class = pygments_class(token_type)
+
+        while value:
+            while segment_index + 1 < len(segments) and segments[segment_index + 1][0] <= position:
+                segment_index += 1
+
+            segment_start, segment = segments[segment_index]
+            segment_end = segment_start + len(segment.astext())
+
+            # Split the token at the end of the segment and at line breaks
+            part = value[: max(segment_end - position, 1)].split("\n", 1)[0]
+            if not part:
+                lines.append([])
+                link = None
+                position += 1
+                value = value[1:]
+                continue
+
+            node = nodes.inline(part, part, classes=[token_class]) if token_class else nodes.Text(part)
+            if isinstance(segment, nodes.Text):
+                lines[-1].append(node)
+                link = None
+            else:
+                if link is None or link[0] is not segment:
+                    link = (segment, segment.copy())
+                    lines[-1].append(link[1])
+                link[1].append(node)
+
+            position += len(part)
+            value = value[len(part) :]
+
+    return lines
+
+
+def lex(text, language):
+    try:
+        lexer = get_lexer_by_name(language or "text", stripnl=False, ensurenl=False)
+    except ClassNotFound:
+        lexer = get_lexer_by_name("text", stripnl=False, ensurenl=False)
+
+    return lexer.get_tokens(text)
+
+
+def pygments_class(token_type):
+    while token_type not in STANDARD_TYPES:
+        token_type = token_type.parent
+
+    return STANDARD_TYPES[token_type]